@@ -0,0 +1,121 @@
+//! Runs Matt Currie's acid2 PPU tests (https://github.com/mattcurrie/dmg-acid2) and compares the
+//! finished frame with the reference image that comes with them.
+//!
+//! The ROMs are not checked in. Point `ACID2_DIR` at a directory holding `dmg-acid2.gb`,
+//! `cgb-acid2.gbc` (https://github.com/mattcurrie/cgb-acid2) and their `reference-dmg.png` and
+//! `reference-cgb.png` (defaults to `test-roms/acid2`), otherwise the tests are skipped. CGB mode
+//! only starts from the CGB boot ROM, so cgb-acid2 also needs a dump of it as `cgb_boot.bin`.
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use crate::cartridge::Cartridge;
+use crate::cpu::Cpu;
+use crate::ppu::{SCREEN_HEIGHT, SCREEN_WIDTH};
+
+const DEFAULT_DIR: &str = "test-roms/acid2";
+
+//the image is drawn in the first few frames, LD B,B marks the end
+const MAX_CYCLES: usize = 4_194_304 * 5;
+
+fn acid2_dir() -> Option<PathBuf> {
+    let dir = std::env::var_os("ACID2_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_DIR));
+    if dir.is_dir() {
+        Some(dir)
+    } else {
+        eprintln!("skipping acid2, {} not found", dir.display());
+        None
+    }
+}
+
+//runs the ROM, after `boot_rom` if there is one, until it hits LD B,B
+fn run(rom: &Path, boot_rom: Option<&Path>) -> Result<Cpu> {
+    let file_name = rom.to_string_lossy();
+    let cartridge = Cartridge::new(&file_name, fs::read(rom)?)?;
+    let mut cpu = match boot_rom {
+        Some(path) => Cpu::with_boot_rom(cartridge, fs::read(path)?)?,
+        None => Cpu::new(cartridge),
+    };
+    cpu.set_test_mode(true);
+    while !cpu.step()?.breakpoint {
+        if cpu.cycles() >= MAX_CYCLES {
+            bail!("{} did not finish", rom.display());
+        }
+    }
+    Ok(cpu)
+}
+
+//the pixels as 15-bit colors like the PPU's frame, gray images repeat their one sample. Both
+//references stretch 5-bit channels to 8 bits (the DMG one has the shades 0xFF, 0xAA, 0x55 and
+//0x00), so dropping the low 3 bits gets the exact colors back
+fn reference_image(path: &Path) -> Result<Vec<u16>> {
+    let mut decoder = png::Decoder::new(File::open(path)?);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info()?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf)?;
+    if (info.width as usize, info.height as usize) != (SCREEN_WIDTH, SCREEN_HEIGHT) {
+        bail!("{} is {}x{}", path.display(), info.width, info.height);
+    }
+
+    let channels = info.color_type.samples();
+    Ok(buf[..info.buffer_size()]
+        .chunks(channels)
+        .map(|pixel| match pixel {
+            [r, g, b, ..] => [*r, *g, *b],
+            [gray, ..] => [*gray; 3],
+            [] => unreachable!(),
+        })
+        .map(|[r, g, b]| u16::from(r >> 3) | u16::from(g >> 3) << 5 | u16::from(b >> 3) << 10)
+        .collect())
+}
+
+//positions of the pixels that differ, for the failure message
+fn differences(actual: &[u16], expected: &[u16]) -> Vec<(usize, usize)> {
+    actual
+        .iter()
+        .zip(expected)
+        .enumerate()
+        .filter(|(_, (actual, expected))| actual != expected)
+        .map(|(i, _)| (i % SCREEN_WIDTH, i / SCREEN_WIDTH))
+        .collect()
+}
+
+//runs `rom` and compares its frame with `reference`
+fn check(rom: &str, reference: &str, boot_rom: Option<&str>) -> Result<()> {
+    let dir = match acid2_dir() {
+        Some(dir) => dir,
+        None => return Ok(()),
+    };
+    let boot_rom = boot_rom.map(|name| dir.join(name));
+    if let Some(path) = boot_rom.as_ref().filter(|path| !path.is_file()) {
+        eprintln!("skipping {}, {} not found", rom, path.display());
+        return Ok(());
+    }
+
+    let cpu = run(&dir.join(rom), boot_rom.as_deref())?;
+    let expected = reference_image(&dir.join(reference)).context("reading the reference image")?;
+
+    let wrong = differences(cpu.bus().ppu.frame(), &expected);
+    assert!(
+        wrong.is_empty(),
+        "{} pixels differ, the first at {:?}",
+        wrong.len(),
+        wrong.first()
+    );
+    Ok(())
+}
+
+#[test]
+fn dmg_acid2() -> Result<()> {
+    check("dmg-acid2.gb", "reference-dmg.png", None)
+}
+
+#[test]
+fn cgb_acid2() -> Result<()> {
+    check("cgb-acid2.gbc", "reference-cgb.png", Some("cgb_boot.bin"))
+}
@@ -0,0 +1,484 @@
+//! A small SM83 assembler for tests and ROM patches. It accepts the mnemonics of `LOOKUP` and
+//! `SECONDARY` with their placeholders filled in (`LD (HL+),A`, `JR NZ,label`, `LDH ($44),A`),
+//! `label:` definitions, `db`/`dw` and `;` comments. Numbers are decimal, `$FF`, `0xFF`, `0FFh`,
+//! `%1010` or `0b1010`, and operands can add and subtract labels and numbers.
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Context, Result};
+
+use crate::cartridge::Cartridge;
+use crate::opcode::{OpcodeUnprefixed, LOOKUP, SECONDARY};
+
+//names that are operands of their own, never labels
+const RESERVED: [&str; 15] = [
+    "A", "B", "C", "D", "E", "H", "L", "AF", "BC", "DE", "HL", "SP", "NZ", "Z", "NC",
+];
+
+/// Assembles `source` as if it was loaded at `origin`.
+pub fn assemble(source: &str, origin: u16) -> Result<Vec<u8>> {
+    let mut assembler = Assembler {
+        labels: HashMap::new(),
+        resolve: false,
+    };
+    //the first pass only finds the labels, every statement has the same size in both
+    assembler.pass(source, origin)?;
+    assembler.resolve = true;
+    assembler.pass(source, origin)
+}
+
+/// Assembles `source` at `addr` in ROM bank `bank` and writes it into `cartridge`. Returns how
+/// many bytes were written.
+pub fn patch(cartridge: &mut Cartridge, bank: usize, addr: u16, source: &str) -> Result<usize> {
+    let bytes = assemble(source, addr)?;
+    cartridge.patch(bank, addr, &bytes)?;
+    Ok(bytes.len())
+}
+
+/// Assembles its lines for a test, at 0x0100 unless another origin is given with `=>`:
+/// `asm!("LD A,$42", "HALT")` or `asm!(0x0150 => "JR NZ,start")`.
+#[cfg(test)]
+macro_rules! asm {
+    ($origin:expr => $($line:expr),+ $(,)?) => {
+        crate::asm::assemble(&[$($line),+].join("\n"), $origin).unwrap()
+    };
+    ($($line:expr),+ $(,)?) => {
+        asm!(0x0100 => $($line),+)
+    };
+}
+
+struct Assembler {
+    labels: HashMap<String, u16>,
+    resolve: bool, //unknown labels are 0 and ranges are not checked until this is set
+}
+
+impl Assembler {
+    fn pass(&mut self, source: &str, origin: u16) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        for (number, line) in source.lines().enumerate() {
+            let addr = origin.wrapping_add(bytes.len() as u16);
+            let encoded = self
+                .line(line, addr)
+                .with_context(|| format!("line {}: {}", number + 1, line.trim()))?;
+            bytes.extend(encoded);
+        }
+        Ok(bytes)
+    }
+
+    fn line(&mut self, line: &str, addr: u16) -> Result<Vec<u8>> {
+        let mut text = line.split(';').next().unwrap_or_default().trim();
+
+        if let Some(colon) = text
+            .find(':')
+            .filter(|&colon| is_identifier(&text[..colon]))
+        {
+            let name = &text[..colon];
+            if !self.resolve && self.labels.insert(name.to_string(), addr).is_some() {
+                bail!("label {} is defined twice", name);
+            }
+            text = text[colon..].trim_start_matches(':').trim();
+        }
+        if text.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (mnemonic, operands) = match text.find(char::is_whitespace) {
+            Some(space) => (&text[..space], split_operands(&text[space..])),
+            None => (text, Vec::new()),
+        };
+        let mnemonic = mnemonic.to_ascii_uppercase();
+
+        match mnemonic.as_str() {
+            "DB" => self.data(&operands, 1),
+            "DW" => self.data(&operands, 2),
+            "RST" => {
+                let vector = match operands.as_slice() {
+                    [vector] => self.value(&parse_expr(vector)?)?,
+                    _ => bail!("RST takes a vector"),
+                };
+                if !(0..=0x38).contains(&vector) || vector % 8 != 0 {
+                    bail!("{:#X} is not an RST vector", vector);
+                }
+                Ok(vec![0xC7 | vector as u8])
+            }
+            //the byte after STOP is not used, most assemblers leave it out
+            "STOP" if operands.is_empty() => Ok(vec![0x10, 0x00]),
+            _ => self.instruction(&mnemonic, &operands, addr),
+        }
+    }
+
+    fn data(&self, operands: &[String], width: usize) -> Result<Vec<u8>> {
+        if operands.is_empty() {
+            bail!("no data");
+        }
+        let mut bytes = Vec::new();
+        for operand in operands {
+            let string = operand
+                .strip_prefix('"')
+                .and_then(|operand| operand.strip_suffix('"'));
+            if let (1, Some(string)) = (width, string) {
+                bytes.extend_from_slice(string.as_bytes());
+                continue;
+            }
+            let value = self.value(&parse_expr(operand)?)?;
+            if width == 1 {
+                bytes.push(self.byte(value)?);
+            } else {
+                bytes.extend_from_slice(&self.word(value)?.to_le_bytes());
+            }
+        }
+        Ok(bytes)
+    }
+
+    fn instruction(&self, mnemonic: &str, operands: &[String], addr: u16) -> Result<Vec<u8>> {
+        let unprefixed = LOOKUP.iter().enumerate().filter(|(_, info)| {
+            !matches!(
+                info.inst,
+                OpcodeUnprefixed::Prefix | OpcodeUnprefixed::Illegal
+            )
+        });
+        for (opcode, info) in unprefixed {
+            if let Some(slots) = match_template(info.mnemonic, mnemonic, operands) {
+                let mut bytes = vec![opcode as u8];
+                for (slot, expr) in slots {
+                    self.encode(slot, &expr, addr, &mut bytes)?;
+                }
+                return Ok(bytes);
+            }
+        }
+        for (opcode, info) in SECONDARY.iter().enumerate() {
+            if match_template(info.mnemonic, mnemonic, operands).is_some() {
+                return Ok(vec![0xCB, opcode as u8]);
+            }
+        }
+        bail!("no form of {} takes these operands", mnemonic)
+    }
+
+    fn encode(&self, slot: Slot, expr: &Expr, addr: u16, bytes: &mut Vec<u8>) -> Result<()> {
+        let value = self.value(expr)?;
+        match slot {
+            Slot::U8 => bytes.push(self.byte(value)?),
+            Slot::U16 | Slot::AddrU16 => bytes.extend_from_slice(&self.word(value)?.to_le_bytes()),
+            Slot::HighAddr => {
+                //either the full address or just the low byte
+                let low = if (0xFF00..=0xFFFF).contains(&value) {
+                    value - 0xFF00
+                } else {
+                    value
+                };
+                if self.resolve && !(0..=0xFF).contains(&low) {
+                    bail!("{:#X} is not in 0xFF00-0xFFFF", value);
+                }
+                bytes.push(low as u8);
+            }
+            Slot::Relative => {
+                let offset = value - (i64::from(addr) + 2);
+                if self.resolve && !(-128..=127).contains(&offset) {
+                    bail!(
+                        "jump to {:#06X} is {} bytes away, JR reaches -128..127",
+                        value,
+                        offset
+                    );
+                }
+                bytes.push(offset as u8);
+            }
+            Slot::Signed | Slot::SpOffset => {
+                if self.resolve && !(-128..=127).contains(&value) {
+                    bail!("{} does not fit in a signed byte", value);
+                }
+                bytes.push(value as u8);
+            }
+        }
+        Ok(())
+    }
+
+    fn byte(&self, value: i64) -> Result<u8> {
+        if self.resolve && !(-128..=0xFF).contains(&value) {
+            bail!("{} does not fit in a byte", value);
+        }
+        Ok(value as u8)
+    }
+
+    fn word(&self, value: i64) -> Result<u16> {
+        if self.resolve && !(-0x8000..=0xFFFF).contains(&value) {
+            bail!("{} does not fit in a word", value);
+        }
+        Ok(value as u16)
+    }
+
+    fn value(&self, expr: &Expr) -> Result<i64> {
+        let mut sum = 0;
+        for (negative, term) in expr {
+            let value = match term {
+                Term::Number(value) => *value,
+                Term::Label(name) => match self.labels.get(name) {
+                    Some(&addr) => i64::from(addr),
+                    None if !self.resolve => 0,
+                    None => bail!("unknown label {}", name),
+                },
+            };
+            sum += if *negative { -value } else { value };
+        }
+        Ok(sum)
+    }
+}
+
+//how an operand placeholder in a mnemonic is encoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    U8,
+    U16,
+    AddrU16,  //(u16)
+    HighAddr, //(a8)
+    Relative, //r8 of JR, written as the target address
+    Signed,   //r8 of ADD SP
+    SpOffset, //SP+r8 of LD HL,SP+r8
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Term {
+    Number(i64),
+    Label(String),
+}
+
+//terms added together, true for the subtracted ones
+type Expr = Vec<(bool, Term)>;
+
+//whether `operands` fit the mnemonic `template`, and the expressions for its placeholders
+fn match_template(
+    template: &str,
+    mnemonic: &str,
+    operands: &[String],
+) -> Option<Vec<(Slot, Expr)>> {
+    let mut parts = template.splitn(2, ' ');
+    if parts.next() != Some(mnemonic) {
+        return None;
+    }
+    let expected: Vec<&str> = parts
+        .next()
+        .map_or(Vec::new(), |rest| rest.split(',').collect());
+    if expected.len() != operands.len() {
+        return None;
+    }
+
+    let mut slots = Vec::new();
+    for (&expected, operand) in expected.iter().zip(operands) {
+        let operand = operand.replace('[', "(").replace(']', ")");
+        let upper = operand.to_ascii_uppercase().replace(' ', "");
+        let inner = operand
+            .strip_prefix('(')
+            .and_then(|inner| inner.strip_suffix(')'));
+
+        let (slot, text) = match expected {
+            "u8" => (Slot::U8, operand.as_str()),
+            "u16" => (Slot::U16, operand.as_str()),
+            "r8" if mnemonic == "JR" => (Slot::Relative, operand.as_str()),
+            "r8" => (Slot::Signed, operand.as_str()),
+            "(u16)" | "(a8)" => match inner {
+                Some(inner) if expected == "(u16)" => (Slot::AddrU16, inner),
+                Some(inner) => (Slot::HighAddr, inner),
+                None => return None,
+            },
+            "SP+r8" => match upper.strip_prefix("SP") {
+                Some(rest) if rest.starts_with('+') || rest.starts_with('-') => {
+                    (Slot::SpOffset, &operand.trim_start()[2..])
+                }
+                _ => return None,
+            },
+            literal if literal == upper => continue,
+            _ => return None,
+        };
+
+        //registers and conditions never fill a placeholder, parse_expr rejects them
+        if text.contains('(') {
+            return None;
+        }
+        slots.push((slot, parse_expr(text).ok()?));
+    }
+    Some(slots)
+}
+
+//splits on commas outside of strings
+fn split_operands(text: &str) -> Vec<String> {
+    let mut operands = Vec::new();
+    let mut current = String::new();
+    let mut in_string = false;
+    for c in text.chars() {
+        match c {
+            '"' => {
+                in_string = !in_string;
+                current.push(c);
+            }
+            ',' if !in_string => operands.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    operands.push(current);
+    operands
+        .iter()
+        .map(|operand| operand.trim().to_string())
+        .collect()
+}
+
+fn parse_expr(text: &str) -> Result<Expr> {
+    let mut expr = Vec::new();
+    let mut negative = false;
+    let mut term = String::new();
+    for c in text.chars().chain(std::iter::once('+')) {
+        match c {
+            '+' | '-' => {
+                let trimmed = term.trim();
+                if trimmed.is_empty() {
+                    //a sign in front of the first term
+                    if !expr.is_empty() {
+                        bail!("missing value in {:?}", text);
+                    }
+                } else {
+                    expr.push((negative, parse_term(trimmed)?));
+                }
+                negative = c == '-';
+                term.clear();
+            }
+            _ => term.push(c),
+        }
+    }
+    if expr.is_empty() {
+        bail!("missing value in {:?}", text);
+    }
+    Ok(expr)
+}
+
+fn parse_term(text: &str) -> Result<Term> {
+    let digits = text.starts_with(|c: char| c.is_ascii_digit());
+    let number = if let Some(hex) = text.strip_prefix('$') {
+        i64::from_str_radix(hex, 16)
+    } else if let Some(bin) = text.strip_prefix('%') {
+        i64::from_str_radix(bin, 2)
+    } else if let (true, Some(hex)) = (digits, text.strip_suffix(&['h', 'H'][..])) {
+        i64::from_str_radix(hex, 16)
+    } else if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        i64::from_str_radix(hex, 16)
+    } else if let Some(bin) = text.strip_prefix("0b").or_else(|| text.strip_prefix("0B")) {
+        i64::from_str_radix(bin, 2)
+    } else if digits {
+        text.parse()
+    } else if is_identifier(text) && !RESERVED.contains(&text.to_ascii_uppercase().as_str()) {
+        return Ok(Term::Label(text.to_string()));
+    } else {
+        bail!("{:?} is not a number or label", text)
+    };
+    number
+        .map(Term::Number)
+        .with_context(|| format!("bad number {:?}", text))
+}
+
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '.')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::test_rom;
+    use crate::disasm::Instruction;
+
+    #[test]
+    fn encodes_lookup_syntax() {
+        assert_eq!(asm!("LD (HL+),A"), [0x22]);
+        assert_eq!(asm!("ld a, [hl-]"), [0x3A]);
+        assert_eq!(asm!("LD BC,$1234"), [0x01, 0x34, 0x12]);
+        assert_eq!(asm!("LD A,(C)", "LD (C),A"), [0xF2, 0xE2]);
+        assert_eq!(
+            asm!("LD ($C000),A", "LD A,($C000)"),
+            [0xEA, 0x00, 0xC0, 0xFA, 0x00, 0xC0]
+        );
+        assert_eq!(
+            asm!("LDH ($FF44),A", "LDH A,($44)"),
+            [0xE0, 0x44, 0xF0, 0x44]
+        );
+        assert_eq!(asm!("LD HL,SP-2", "ADD SP,5"), [0xF8, 0xFE, 0xE8, 0x05]);
+        assert_eq!(asm!("JP HL", "JP $0150"), [0xE9, 0xC3, 0x50, 0x01]);
+        assert_eq!(asm!("RST $38", "RST 08H"), [0xFF, 0xCF]);
+        assert_eq!(asm!("STOP", "STOP $01"), [0x10, 0x00, 0x10, 0x01]);
+        assert_eq!(asm!("BIT 7,(HL)", "SWAP A"), [0xCB, 0x7E, 0xCB, 0x37]);
+        assert_eq!(asm!("DEC (HL)"), [0x35]);
+    }
+
+    #[test]
+    fn resolves_labels_and_data() {
+        let program = asm!(0x0150 =>
+            "start:",
+            "    JR NZ,start ; back to the top",
+            "    CALL func",
+            "    JR C,end",
+            "func: RET",
+            "end::",
+            "    db 1, $FF, \"ok\"",
+            "    dw end, func+1",
+        );
+        assert_eq!(
+            program,
+            [
+                0x20, 0xFE, //JR NZ,$0150
+                0xCD, 0x57, 0x01, //CALL $0157
+                0x38, 0x01, //JR C,$0158
+                0xC9, //RET
+                0x01, 0xFF, b'o', b'k', //db
+                0x58, 0x01, 0x58, 0x01, //dw
+            ]
+        );
+    }
+
+    #[test]
+    fn round_trips_through_the_decoder() {
+        for (opcode, info) in LOOKUP.iter().enumerate() {
+            if matches!(
+                info.inst,
+                OpcodeUnprefixed::Prefix | OpcodeUnprefixed::Illegal
+            ) {
+                continue;
+            }
+            let bytes = [opcode as u8, 0x12, 0x34];
+            let instruction = Instruction::decode(&bytes, 0x0150).unwrap();
+            let source = instruction.to_string();
+            let assembled = assemble(&source, 0x0150).unwrap();
+            assert_eq!(assembled, &bytes[..assembled.len()], "{}", source);
+        }
+        for opcode in 0..=0xFF {
+            let source = Instruction::decode(&[0xCB, opcode], 0).unwrap().to_string();
+            assert_eq!(assemble(&source, 0).unwrap(), [0xCB, opcode], "{}", source);
+        }
+    }
+
+    #[test]
+    fn reports_errors_with_line() {
+        let forward = assemble("NOP\nJR far\nfar: NOP", 0).unwrap();
+        assert_eq!(forward, [0x00, 0x18, 0x00, 0x00]);
+
+        let error = assemble("NOP\nLD A,missing", 0).unwrap_err();
+        assert!(format!("{:#}", error).starts_with("line 2: LD A,missing"));
+        assert!(assemble("LD A,$100", 0).is_err());
+        assert!(assemble("LD (HL),(HL)", 0).is_err());
+        assert!(assemble("RST $39", 0).is_err());
+        assert!(assemble("x:\nx:", 0).is_err());
+
+        let jr_too_far = format!("JR end\nfiller: db {}\nend:", vec!["0"; 200].join(","));
+        assert!(assemble(&jr_too_far, 0).is_err());
+    }
+
+    #[test]
+    fn patches_cartridge() -> Result<()> {
+        let mut cartridge = Cartridge::new("test.gb", test_rom(0x01, 4))?;
+        let written = patch(&mut cartridge, 2, 0x4000, "loop: INC A\nJR loop")?;
+        assert_eq!(written, 3);
+
+        cartridge.write_rom(0x2000, 2);
+        assert_eq!(&cartridge.cur_bank()[..3], &[0x3C, 0x18, 0xFD]);
+
+        assert!(patch(&mut cartridge, 1, 0x0100, "NOP").is_err());
+        assert!(patch(&mut cartridge, 2, 0x7FFF, "JP $0150").is_err());
+        Ok(())
+    }
+}
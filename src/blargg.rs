@@ -0,0 +1,199 @@
+//! Headless runner for Blargg's test ROMs (cpu_instrs, instr_timing, mem_timing, halt_bug, ...).
+//!
+//! The ROMs print their results to the serial port. The newer ones also mirror them into
+//! cartridge RAM: a status byte at 0xA000 (0x80 while running, 0x00 on success), the signature
+//! DE B0 61 at 0xA001 and zero-terminated text from 0xA004.
+
+use crate::cartridge::Cartridge;
+use crate::cpu::Cpu;
+use crate::mmu::Bus;
+
+const SIGNATURE: [u8; 3] = [0xDE, 0xB0, 0x61];
+const STATUS_RUNNING: u8 = 0x80;
+
+//two minutes of emulated time, cpu_instrs needs a bit under one
+pub const DEFAULT_MAX_CYCLES: usize = 4_194_304 * 120;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Passed,
+    Failed,
+    TimedOut,
+}
+
+impl Verdict {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Verdict::Passed => 0,
+            Verdict::Failed => 1,
+            Verdict::TimedOut => 2,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Report {
+    pub verdict: Verdict,
+    pub output: String, //serial output, or the text at 0xA004 if the ROM only reported there
+    pub cycles: usize,
+}
+
+fn ram_result(cpu: &Cpu) -> Option<(u8, String)> {
+    let bus = cpu.bus();
+    let signature = [
+        bus.peek_byte(0xA001),
+        bus.peek_byte(0xA002),
+        bus.peek_byte(0xA003),
+    ];
+    if signature != SIGNATURE {
+        return None;
+    }
+
+    let text = (0xA004..0xC000)
+        .map(|addr| bus.peek_byte(addr))
+        .take_while(|&byte| byte != 0)
+        .map(char::from)
+        .collect();
+    Some((bus.peek_byte(0xA000), text))
+}
+
+fn serial_verdict(output: &str) -> Option<Verdict> {
+    if output.contains("Passed") {
+        Some(Verdict::Passed)
+    } else if output.contains("Failed") {
+        Some(Verdict::Failed)
+    } else {
+        None
+    }
+}
+
+/// Runs the ROM until it reports a result or `max_cycles` clock cycles have passed.
+pub fn run(cartridge: Cartridge, max_cycles: usize) -> Report {
+    let mut cpu = Cpu::new(cartridge);
+
+    let verdict = loop {
+        if let Err(err) = cpu.step() {
+            let mut output = String::from_utf8_lossy(&cpu.bus().serial.output).into_owned();
+            output.push_str(&format!("\n{}", err));
+            return Report {
+                verdict: Verdict::Failed,
+                output,
+                cycles: cpu.cycles(),
+            };
+        }
+
+        let output = String::from_utf8_lossy(&cpu.bus().serial.output);
+        if let Some(verdict) = serial_verdict(&output) {
+            break verdict;
+        }
+
+        if let Some((status, _)) = ram_result(&cpu) {
+            if status != STATUS_RUNNING {
+                break if status == 0 {
+                    Verdict::Passed
+                } else {
+                    Verdict::Failed
+                };
+            }
+        }
+
+        if cpu.cycles() >= max_cycles {
+            break Verdict::TimedOut;
+        }
+    };
+
+    let serial_output = String::from_utf8_lossy(&cpu.bus().serial.output).into_owned();
+    let output = match ram_result(&cpu) {
+        Some((_, text)) if serial_output.is_empty() => text,
+        _ => serial_output,
+    };
+
+    Report {
+        verdict,
+        output,
+        cycles: cpu.cycles(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::{test_rom, with_program};
+    use crate::util::find_roms;
+    use anyhow::Result;
+    use std::path::PathBuf;
+
+    const DEFAULT_DIR: &str = "test-roms/blargg";
+
+    fn cartridge_with_program(cartridge_type: u8, ram_size: u8, program: &[u8]) -> Cartridge {
+        let mut rom = test_rom(cartridge_type, 2);
+        rom[0x149] = ram_size;
+        Cartridge::new("test.gb", with_program(rom, program)).unwrap()
+    }
+
+    #[test]
+    fn detects_serial_result() {
+        let mut program = Vec::new();
+        for &byte in b"Passed" {
+            //LD A,u8; LDH (01),A; LD A,0x81; LDH (02),A
+            program.extend_from_slice(&[0x3E, byte, 0xE0, 0x01, 0x3E, 0x81, 0xE0, 0x02]);
+        }
+        program.extend_from_slice(&[0x18, 0xFE]); //JR -2
+
+        let report = run(cartridge_with_program(0x00, 0x00, &program), 100_000);
+        assert_eq!(report.verdict, Verdict::Passed);
+        assert_eq!(report.output, "Passed");
+    }
+
+    #[test]
+    fn detects_ram_signature() {
+        let mut program = vec![0x3E, 0x0A, 0xEA, 0x00, 0x00]; //enable cartridge RAM
+        program.extend_from_slice(&[0x3E, 0x80, 0xEA, 0x00, 0xA0]); //status: running
+        let text = b"\xDE\xB0\x61Failed";
+        for (offset, &byte) in text.iter().enumerate() {
+            let [hi, lo] = (0xA001 + offset as u16).to_be_bytes();
+            program.extend_from_slice(&[0x3E, byte, 0xEA, lo, hi]); //LD A,u8; LD (u16),A
+        }
+        program.extend_from_slice(&[0x3E, 0x01, 0xEA, 0x00, 0xA0]); //status 1
+        program.extend_from_slice(&[0x18, 0xFE]);
+
+        let report = run(cartridge_with_program(0x03, 0x02, &program), 100_000);
+        assert_eq!(report.verdict, Verdict::Failed);
+        assert_eq!(report.output, "Failed");
+    }
+
+    #[test]
+    fn times_out() {
+        let report = run(cartridge_with_program(0x00, 0x00, &[0x18, 0xFE]), 1_000);
+        assert_eq!(report.verdict, Verdict::TimedOut);
+    }
+
+    /// Runs every ROM under `BLARGG_ROMS_DIR` (defaults to `test-roms/blargg`), skipped when the
+    /// ROMs are not present.
+    #[test]
+    fn blargg_roms() -> Result<()> {
+        let dir = std::env::var_os("BLARGG_ROMS_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_DIR));
+        if !dir.is_dir() {
+            eprintln!("skipping Blargg test ROMs, {} not found", dir.display());
+            return Ok(());
+        }
+
+        let roms = find_roms(&dir)?;
+
+        let mut failed = Vec::new();
+        for path in &roms {
+            let cartridge = Cartridge::new(&path.to_string_lossy(), std::fs::read(path)?)?;
+            let report = run(cartridge, DEFAULT_MAX_CYCLES);
+            eprintln!("{}: {:?}", path.display(), report.verdict);
+            if report.verdict != Verdict::Passed {
+                eprintln!("{}", report.output);
+                failed.push(path.display().to_string());
+            }
+        }
+
+        assert!(failed.is_empty(), "failed: {}", failed.join(", "));
+        Ok(())
+    }
+}
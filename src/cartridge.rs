@@ -4,9 +4,10 @@ use std::{convert::TryInto, fmt::Debug, str::from_utf8};
 use anyhow::{bail, Context, Result};
 
 const BANK_SIZE: usize = 16 * 1024;
+const RAM_BANK_SIZE: usize = 8 * 1024;
 type Bank = [u8; BANK_SIZE];
 
-const NINTENDO_LOGO: [u8; 48] = [
+pub const NINTENDO_LOGO: [u8; 48] = [
     0xCE, 0xED, 0x66, 0x66, 0xCC, 0x0D, 0x00, 0x0B, 0x03, 0x73, 0x00, 0x83, 0x00, 0x0C, 0x00, 0x0D,
     0x00, 0x08, 0x11, 0x1F, 0x88, 0x89, 0x00, 0x0E, 0xDC, 0xCC, 0x6E, 0xE6, 0xDD, 0xDD, 0xD9, 0x99,
     0xBB, 0xBB, 0x67, 0x63, 0x6E, 0x0E, 0xEC, 0xCC, 0xDD, 0xDC, 0x99, 0x9F, 0xBB, 0xB9, 0x33, 0x3E,
@@ -17,10 +18,26 @@ pub struct Cartridge {
     file_name: String,
     rom: Vec<u8>,
     num_banks: usize,
+    zero_bank: usize, //bank mapped at 0x0000, only MBC1 in advanced mode changes it
     cur_bank: usize,
+    ram: Vec<u8>,
+    ram_bank: usize,
+    ram_enabled: bool,
+    mbc: Mbc,
     pub header: CartridgeHeader,
 }
 
+//state of the bank controller registers, written through the ROM address space
+#[derive(Debug)]
+enum Mbc {
+    None,
+    Mbc1 {
+        bank_lo: u8,         //5 bits, 0x2000-0x3FFF
+        bank_hi: u8,         //2 bits, 0x4000-0x5FFF
+        advanced_mode: bool, //0x6000-0x7FFF, bank_hi also applies to 0x0000 and RAM
+    },
+}
+
 #[derive(Debug, TryFromPrimitive)]
 #[repr(u8)]
 #[allow(non_camel_case_types)]
@@ -63,6 +80,7 @@ pub struct CartridgeHeader {
     cartridge_type: CartridgeType,
     rom_banks: u8,
     ram_banks: u8, //each RAM bank is 8KB
+    header_checksum: u8,
 }
 
 impl Debug for CartridgeHeader {
@@ -86,6 +104,7 @@ impl Debug for CartridgeHeader {
             .field("cartridge_type", &self.cartridge_type)
             .field("rom_banks", &self.rom_banks)
             .field("ram_banks", &self.ram_banks)
+            .field("header_checksum", &self.header_checksum)
             .finish()
     }
 }
@@ -103,7 +122,6 @@ impl CartridgeHeader {
         let title: [u8; 16] = rom[0x0134..=0x0143].try_into()?;
 
         let uses_new_licensee = rom[0x14B] == 0x33;
-        dbg!(&uses_new_licensee);
 
         let licensee_code = if uses_new_licensee {
             let new_licensee_bits = &rom[0x144..=0x145];
@@ -112,8 +130,6 @@ impl CartridgeHeader {
             rom[0x148]
         };
 
-        dbg!(&rom[0x144..=0x145]);
-
         let cgb_support = uses_new_licensee && (title[15] == 0x80 || title[15] == 0xC0); //TODO: C0 should disallow old Gameboy from running this
 
         let sgb_support = rom[0x146] == 0x03;
@@ -140,6 +156,8 @@ impl CartridgeHeader {
             _ => bail!("invalid value for number of ram banks"),
         };
 
+        let header_checksum = rom[0x14D];
+
         let header = CartridgeHeader {
             title,
             cgb_support,
@@ -148,17 +166,31 @@ impl CartridgeHeader {
             cartridge_type,
             rom_banks,
             ram_banks,
+            header_checksum,
         };
 
         Ok(header)
     }
+
+    pub fn cgb_support(&self) -> bool {
+        self.cgb_support
+    }
+
+    pub fn sgb_support(&self) -> bool {
+        self.sgb_support
+    }
+
+    //as stored at 0x014D, not verified
+    pub fn header_checksum(&self) -> u8 {
+        self.header_checksum
+    }
 }
 
 impl Cartridge {
     pub fn new(file_name: &str, rom: Vec<u8>) -> Result<Cartridge> {
         let data_len = rom.len();
 
-        let rom = if data_len % BANK_SIZE == 0 {
+        let rom = if data_len.is_multiple_of(BANK_SIZE) {
             rom
         } else {
             bail!("ROM size not alligned to BANK_SIZE")
@@ -172,18 +204,93 @@ impl Cartridge {
 
         let header = CartridgeHeader::from_rom(&rom)?;
 
+        let ram = vec![0; usize::from(header.ram_banks) * RAM_BANK_SIZE];
+
+        let mbc = match header.cartridge_type {
+            CartridgeType::ROM_ONLY | CartridgeType::ROM_RAM | CartridgeType::ROM_RAM_BATTERY => {
+                Mbc::None
+            }
+            CartridgeType::MBC1 | CartridgeType::MBC1_RAM | CartridgeType::MBC1_RAM_BATTERY => {
+                Mbc::Mbc1 {
+                    bank_lo: 1,
+                    bank_hi: 0,
+                    advanced_mode: false,
+                }
+            }
+            //other controllers are not emulated yet, their ROM runs with bank 1 fixed at 0x4000
+            _ => Mbc::None,
+        };
+
+        //RAM without a controller is always accessible
+        let ram_enabled = matches!(mbc, Mbc::None);
+
         let cart = Self {
             file_name,
             rom,
             num_banks,
+            zero_bank: 0,
             cur_bank,
+            ram,
+            ram_bank: 0,
+            ram_enabled,
+            mbc,
             header,
         };
         Ok(cart)
     }
 
+    /// Handles a write to 0x0000-0x7FFF, which goes to the bank controller.
+    pub fn write_rom(&mut self, addr: u16, data: u8) {
+        if let Mbc::Mbc1 {
+            bank_lo,
+            bank_hi,
+            advanced_mode,
+        } = &mut self.mbc
+        {
+            match addr {
+                0x0000..=0x1FFF => self.ram_enabled = data & 0x0F == 0x0A,
+                0x2000..=0x3FFF => *bank_lo = (data & 0b1_1111).max(1),
+                0x4000..=0x5FFF => *bank_hi = data & 0b11,
+                0x6000..=0x7FFF => *advanced_mode = data & 1 == 1,
+                _ => unreachable!("{:#06X} is not in ROM", addr),
+            }
+
+            let hi_bits = usize::from(*bank_hi) << 5;
+            self.cur_bank = (hi_bits | usize::from(*bank_lo)) % self.num_banks;
+            if *advanced_mode {
+                self.zero_bank = hi_bits % self.num_banks;
+                self.ram_bank = usize::from(*bank_hi);
+            } else {
+                self.zero_bank = 0;
+                self.ram_bank = 0;
+            }
+        }
+    }
+
+    /// Overwrites ROM at `addr` as seen with `bank` mapped, bank 0 for 0x0000-0x3FFF.
+    pub fn patch(&mut self, bank: usize, addr: u16, bytes: &[u8]) -> Result<()> {
+        let (start, end) = match addr {
+            0x0000..=0x3FFF if bank == 0 => (usize::from(addr), 0x4000),
+            0x4000..=0x7FFF if bank > 0 => (usize::from(addr) - 0x4000, 0x8000),
+            _ => bail!("{:#06X} is not in ROM bank {}", addr, bank),
+        };
+        if usize::from(addr) + bytes.len() > end {
+            bail!(
+                "{} bytes at {:#06X} run past the end of the bank",
+                bytes.len(),
+                addr
+            );
+        }
+
+        let rom = self
+            .nth_bank_mut(bank)
+            .with_context(|| format!("no ROM bank {}", bank))?;
+        rom[start..start + bytes.len()].copy_from_slice(bytes);
+        Ok(())
+    }
+
     pub fn bank0(&self) -> &[u8] {
-        self.nth_bank(0).expect("could not get bank")
+        self.nth_bank(self.zero_bank).expect("could not get bank")
     }
 
     pub fn bank0_mut(&mut self) -> &mut [u8] {
@@ -199,10 +306,33 @@ impl Cartridge {
             .expect("could not get bank")
     }
 
+    //offset is relative to 0xA000, reads with RAM disabled or absent float high
+    pub fn read_ram(&self, offset: u16) -> u8 {
+        match self.ram_index(offset) {
+            Some(index) if self.ram_enabled => self.ram[index],
+            _ => 0xFF,
+        }
+    }
+
+    pub fn write_ram(&mut self, offset: u16, data: u8) {
+        match self.ram_index(offset) {
+            Some(index) if self.ram_enabled => self.ram[index] = data,
+            _ => {}
+        }
+    }
+
+    fn ram_index(&self, offset: u16) -> Option<usize> {
+        if self.ram.is_empty() {
+            return None;
+        }
+        let index = self.ram_bank * RAM_BANK_SIZE + usize::from(offset);
+        Some(index % self.ram.len())
+    }
+
     fn nth_bank(&self, bank_num: usize) -> Option<&[u8]> {
         match bank_num {
-            n if n <= self.num_banks => {
-                let bank = &self.rom[(bank_num * BANK_SIZE)..=((bank_num + 1) * BANK_SIZE)];
+            n if n < self.num_banks => {
+                let bank = &self.rom[(bank_num * BANK_SIZE)..((bank_num + 1) * BANK_SIZE)];
                 Some(bank)
             }
             _ => None,
@@ -211,8 +341,8 @@ impl Cartridge {
 
     fn nth_bank_mut(&mut self, bank_num: usize) -> Option<&mut [u8]> {
         match bank_num {
-            n if n <= self.num_banks => {
-                let bank = &mut self.rom[(bank_num * BANK_SIZE)..=((bank_num + 1) * BANK_SIZE)];
+            n if n < self.num_banks => {
+                let bank = &mut self.rom[(bank_num * BANK_SIZE)..((bank_num + 1) * BANK_SIZE)];
                 Some(bank)
             }
             _ => None,
@@ -220,6 +350,70 @@ impl Cartridge {
     }
 }
 
+#[cfg(test)]
+pub fn test_rom(cartridge_type: u8, num_banks: usize) -> Vec<u8> {
+    let mut rom = vec![0; num_banks * BANK_SIZE];
+    rom[0x104..=0x133].copy_from_slice(&NINTENDO_LOGO);
+    rom[0x147] = cartridge_type;
+    rom[0x148] = (num_banks.trailing_zeros() - 1) as u8;
+    for bank in 0..num_banks {
+        rom[bank * BANK_SIZE + 0x200] = bank as u8;
+    }
+    rom
+}
+
+//enters `program` at 0x0150, past the header
+#[cfg(test)]
+pub fn with_program(mut rom: Vec<u8>, program: &[u8]) -> Vec<u8> {
+    rom[0x100..0x104].copy_from_slice(&[0x00, 0xC3, 0x50, 0x01]); //NOP; JP 0x0150
+    rom[0x150..0x150 + program.len()].copy_from_slice(program);
+    rom
+}
+
+#[test]
+fn mbc1_bank_switching() -> Result<()> {
+    let mut cart = Cartridge::new("test.gb", test_rom(0x01, 64))?;
+    assert_eq!(cart.cur_bank()[0x200], 1);
+
+    cart.write_rom(0x2000, 0x00); //bank 0 selects bank 1
+    assert_eq!(cart.cur_bank()[0x200], 1);
+
+    cart.write_rom(0x2000, 0x05);
+    cart.write_rom(0x4000, 0x01);
+    assert_eq!(cart.cur_bank()[0x200], 0x25);
+    assert_eq!(cart.bank0()[0x200], 0);
+
+    cart.write_rom(0x6000, 0x01);
+    assert_eq!(cart.bank0()[0x200], 0x20);
+    Ok(())
+}
+
+#[test]
+fn mbc1_ram_needs_enabling() -> Result<()> {
+    let mut rom = test_rom(0x03, 4);
+    rom[0x149] = 0x02;
+    let mut cart = Cartridge::new("test.gb", rom)?;
+
+    cart.write_ram(0x0010, 0x42);
+    assert_eq!(cart.read_ram(0x0010), 0xFF);
+
+    cart.write_rom(0x0000, 0x0A);
+    cart.write_ram(0x0010, 0x42);
+    assert_eq!(cart.read_ram(0x0010), 0x42);
+    Ok(())
+}
+
+#[test]
+fn other_controllers_still_load() -> Result<()> {
+    for cartridge_type in [0x13, 0x19] {
+        //MBC3+RAM+BATTERY, MBC5
+        let mut cart = Cartridge::new("test.gb", test_rom(cartridge_type, 8))?;
+        cart.write_rom(0x2000, 0x05);
+        assert_eq!(cart.cur_bank()[0x200], 1);
+    }
+    Ok(())
+}
+
 #[test]
 fn parsing_new_licensee_bits() -> Result<()> {
     let new_licensee_bits: [u8; 2] = [0x31, 0x32];
@@ -1,7 +1,12 @@
+use std::io::Write;
 use std::ops::{BitAnd, BitOr, Index, IndexMut, Not, Shl, ShlAssign, Shr, ShrAssign};
 use thiserror::Error;
 
 use crate::cartridge::Cartridge;
+use crate::disasm::{Instruction, OpInfo, Operand};
+use crate::hle_boot;
+use crate::mmu::{Bus, Mmu, INT_JOYPAD};
+use crate::model::Model;
 use crate::util::RegisterPair;
 use crate::util::Twiddling;
 use anyhow::{bail, Result};
@@ -12,29 +17,92 @@ const M: usize = 4;
 
 const START_ADDR: u16 = 0x100;
 
-#[derive(Error, Debug)]
+const INTERRUPT_VECTORS: [u16; 5] = [0x40, 0x48, 0x50, 0x58, 0x60];
+
+#[derive(Error, Debug, PartialEq, Eq)]
 pub enum CpuError {
-    #[error("illegal opcode {0} called")]
+    #[error("illegal opcode {0:#04X} called")]
     IllegalOpcode(u8),
+    #[error("CPU is locked up after illegal opcode {0:#04X}")]
+    LockedUp(u8),
     #[error("opcode called with bad argument")]
     BadArgument,
+    #[error("writing the trace failed: {0}")]
+    TraceFailed(std::io::ErrorKind),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepInfo {
+    pub pc: u16,          //address of the instruction that was executed
+    pub cycles: usize,    //clock cycles spent, including interrupt dispatch and halted cycles
+    pub breakpoint: bool, //executed LD B,B in test mode
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BusActivity {
+    Idle,
+    Read(u16, u8),
+    Write(u16, u8),
+}
+
+impl std::fmt::Display for BusActivity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BusActivity::Idle => write!(f, "idle"),
+            BusActivity::Read(addr, data) => write!(f, "read {:#06X} -> {:#04X}", addr, data),
+            BusActivity::Write(addr, data) => write!(f, "write {:#06X} <- {:#04X}", addr, data),
+        }
+    }
+}
+
+/// Flat 64 KiB of RAM with nothing else attached, handy for exercising the CPU on its own.
+/// Records what happened on the bus during every M-cycle.
+pub struct CpuRam {
+    mem: Box<[u8; 65536]>,
+    pub activity: Vec<BusActivity>,
+}
+
+impl CpuRam {
+    pub fn new() -> Self {
+        Self {
+            mem: Box::new([0; 65536]),
+            activity: Vec::new(),
+        }
+    }
+
+    pub fn load(&mut self, addr: u16, data: &[u8]) {
+        let start = usize::from(addr);
+        self.mem[start..start + data.len()].copy_from_slice(data);
+    }
+
+    //the M-cycle was already ticked, the access fills it in
+    fn record(&mut self, activity: BusActivity) {
+        match self.activity.last_mut() {
+            Some(last @ BusActivity::Idle) => *last = activity,
+            _ => self.activity.push(activity),
+        }
+    }
 }
 
-struct CpuRam([u8; 65536]);
+impl Default for CpuRam {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl Index<u16> for CpuRam {
     type Output = u8;
 
     fn index(&self, index: u16) -> &Self::Output {
         let index = index as usize;
-        &self.0[index]
+        &self.mem[index]
     }
 }
 
 impl IndexMut<u16> for CpuRam {
     fn index_mut(&mut self, index: u16) -> &mut Self::Output {
         let index = index as usize;
-        &mut self.0[index]
+        &mut self.mem[index]
     }
 }
 
@@ -42,7 +110,28 @@ impl std::ops::Deref for CpuRam {
     type Target = [u8; 65536];
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.mem
+    }
+}
+
+impl Bus for CpuRam {
+    fn read_byte(&mut self, addr: u16) -> u8 {
+        let data = self[addr];
+        self.record(BusActivity::Read(addr, data));
+        data
+    }
+
+    fn write_byte(&mut self, addr: u16, data: u8) {
+        self.record(BusActivity::Write(addr, data));
+        self[addr] = data;
+    }
+
+    fn peek_byte(&self, addr: u16) -> u8 {
+        self[addr]
+    }
+
+    fn tick(&mut self) {
+        self.activity.push(BusActivity::Idle);
     }
 }
 
@@ -68,10 +157,10 @@ impl Flags {
 
     #[inline(always)]
     pub fn set_from_byte(&mut self, value: u8) {
-        self.zero = value.get_bit(4);
-        self.neg = value.get_bit(5);
-        self.half_carry = value.get_bit(6);
-        self.carry = value.get_bit(7);
+        self.zero = value.get_bit(7);
+        self.neg = value.get_bit(6);
+        self.half_carry = value.get_bit(5);
+        self.carry = value.get_bit(4);
     }
 }
 
@@ -87,19 +176,6 @@ pub struct Registers {
 }
 
 impl Registers {
-    pub fn with_boot_values() -> Self {
-        let mut registers = Registers::default();
-
-        registers.set_af(0x01B0);
-        registers.bc.set_both(0x0013);
-        registers.de.set_both(0x00D8);
-        registers.hl.set_both(0x014D);
-        registers.pc = 0x0100;
-        registers.sp = 0xFFFE;
-
-        registers
-    }
-
     pub fn af(&self) -> u16 {
         let f = self.flags.f_reg() as u16;
         let a = self.a as u16;
@@ -115,147 +191,343 @@ impl Registers {
     pub fn decimal_adjust_a(&mut self) {
         //adapted from https://ehaskins.com/2018-01-30%20Z80%20DAA/ and https://github.com/mvdnes/rboy/blob/master/src/cpu.rs
 
-        let mut adjust = if self.flags.carry { 0x60 } else { 0x00 };
-
-        if self.flags.half_carry {
-            adjust |= 0x06;
-        }
-
         if !self.flags.neg {
-            if self.a & 0x0F > 0x09 {
-                adjust |= 0x06;
+            if self.flags.carry || self.a > 0x99 {
+                self.a = self.a.wrapping_add(0x60);
+                self.flags.carry = true;
             }
-            if self.a > 0x99 {
-                adjust |= 0x60;
+            if self.flags.half_carry || self.a & 0x0F > 0x09 {
+                self.a = self.a.wrapping_add(0x06);
             }
-            self.a = self.a.wrapping_add(adjust);
         } else {
-            self.a = self.a.wrapping_sub(adjust);
-        }
-
-        if self.a == 0 {
-            self.flags.zero = true;
+            if self.flags.carry {
+                self.a = self.a.wrapping_sub(0x60);
+            }
+            if self.flags.half_carry {
+                self.a = self.a.wrapping_sub(0x06);
+            }
         }
 
-        if adjust >= 0x60 {
-            self.flags.carry = true;
-        }
+        self.flags.zero = self.a == 0;
+        self.flags.half_carry = false;
     }
 }
 
-pub struct Cpu {
+pub struct Cpu<B: Bus = Mmu> {
     registers: Registers,
 
-    // ram: CpuRam,
-    cartridge: Cartridge,
+    bus: B,
 
     ime: bool,
+    ime_scheduled: bool, //EI takes effect after the following instruction
+    halted: bool,
+    halt_bug: bool, //next opcode fetch does not increment PC
     stopped: bool,
+    locked: Option<u8>, //opcode that hung the CPU
+    test_mode: bool,    //treat LD B,B as a breakpoint, the way the mooneye test ROMs expect
+    breakpoint: bool,
+    trace: Option<Box<dyn Write>>, //gets a trace_line before every instruction
+    operand: Option<Operand>,      //immediate of the instruction being executed
 
     cycles_passed: usize,
 }
 
-impl Cpu {
+impl Cpu<Mmu> {
     pub fn debug_header(&self) {
-        println!("{:?}", self.cartridge.header);
+        println!("{:?}", self.bus.cartridge.header);
     }
 
+    /// Starts at 0x0100 in the state the DMG boot ROM leaves. CGB cartridges run in DMG mode,
+    /// CGB mode needs the CGB boot ROM, see `with_boot_rom`.
     pub fn new(cartridge: Cartridge) -> Self {
+        Self::with_model(cartridge, Model::Dmg)
+    }
+
+    pub fn with_model(cartridge: Cartridge, model: Model) -> Self {
+        let registers = model.boot_registers(cartridge.header.header_checksum());
+        Self::with_bus(registers, Mmu::with_model(cartridge, model))
+    }
+
+    /// Plays the boot animation without a boot ROM, then continues like `with_model`.
+    pub fn with_hle_boot(cartridge: Cartridge, model: Model) -> Result<Self> {
+        let registers = model.boot_registers(cartridge.header.header_checksum());
+        let mut mmu = Mmu::new(cartridge);
+        let m_cycles = hle_boot::run(&mut mmu)?;
+        mmu.finish_boot(model);
+
+        let mut cpu = Self::with_bus(registers, mmu);
+        cpu.cycles_passed = m_cycles * M;
+        Ok(cpu)
+    }
+
+    /// Starts from power on at 0x0000 and runs `boot_rom` before handing over to the cartridge.
+    pub fn with_boot_rom(cartridge: Cartridge, boot_rom: Vec<u8>) -> Result<Self> {
+        let mmu = Mmu::with_boot_rom(cartridge, boot_rom)?;
+        Ok(Self::with_bus(Registers::default(), mmu))
+    }
+}
+
+impl<B: Bus> Cpu<B> {
+    //clock cycles since power on
+    pub fn cycles(&self) -> usize {
+        self.cycles_passed
+    }
+
+    pub fn registers(&self) -> &Registers {
+        &self.registers
+    }
+
+    pub fn bus(&self) -> &B {
+        &self.bus
+    }
+
+    pub fn bus_mut(&mut self) -> &mut B {
+        &mut self.bus
+    }
+
+    //a pending EI counts as enabled
+    pub fn ime(&self) -> bool {
+        self.ime || self.ime_scheduled
+    }
+
+    pub fn set_ime(&mut self, ime: bool) {
+        self.ime = ime;
+    }
+
+    pub fn set_test_mode(&mut self, test_mode: bool) {
+        self.test_mode = test_mode;
+    }
+
+    /// Logs the CPU state before every instruction to `trace`, in the format used by
+    /// gameboy-doctor. `None` turns tracing off.
+    pub fn set_trace(&mut self, trace: Option<Box<dyn Write>>) {
+        self.trace = trace;
+    }
+
+    /// Decodes the instruction at PC without touching the bus.
+    pub fn next_instruction(&self) -> Instruction {
+        Instruction::decode_with(self.registers.pc, |addr| self.bus.peek_byte(addr))
+    }
+
+    /// The state about to be logged, e.g.
+    /// `A:01 F:B0 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0100 PCMEM:00,C3,13,02`
+    pub fn trace_line(&self) -> String {
+        let r = &self.registers;
+        let pc = r.pc;
+        let mem = |offset: u16| self.bus.peek_byte(pc.wrapping_add(offset));
+        format!(
+            "A:{:02X} F:{:02X} B:{:02X} C:{:02X} D:{:02X} E:{:02X} H:{:02X} L:{:02X} SP:{:04X} PC:{:04X} PCMEM:{:02X},{:02X},{:02X},{:02X}",
+            r.a,
+            r.flags.f_reg(),
+            r.bc.hi,
+            r.bc.lo,
+            r.de.hi,
+            r.de.lo,
+            r.hl.hi,
+            r.hl.lo,
+            r.sp,
+            pc,
+            mem(0),
+            mem(1),
+            mem(2),
+            mem(3)
+        )
+    }
+
+    pub fn with_bus(registers: Registers, bus: B) -> Self {
         Self {
-            registers: Registers::with_boot_values(),
-            // ram,
-            cartridge,
+            registers,
+            bus,
             ime: false,
+            ime_scheduled: false,
+            halted: false,
+            halt_bug: false,
             stopped: false,
+            locked: None,
+            test_mode: false,
+            breakpoint: false,
+            trace: None,
+            operand: None,
             cycles_passed: 0,
         }
     }
 
-    fn read_byte(&self, addr: u16) -> u8 {
-        match addr {
-            0x0000..=0x3FFF => self.cartridge.bank0()[usize::from(addr)], //16 KiB ROM bank 00
-            0x4000..=0x7FFF => {
-                let offset = addr - 0x4000;
-                self.cartridge.cur_bank()[usize::from(offset)]
-            } //16 KiB ROM Bank 01~NN
-            0x8000..=0x9FFF => todo!(),                                   //8 KiB Video RAM (VRAM)
-            0xA000..=0xBFFF => todo!(),                                   //8 KiB External RAM
-            0xC000..=0xCFFF => todo!(),                                   //4 KiB Work RAM (WRAM)
-            0xD000..=0xDFFF => todo!(),                                   //4 KiB Work RAM (WRAM)
-            0xE000..=0xFDFF => todo!(), //Mirror of C000~DDFF (ECHO RAM)
-            0xFE00..=0xFE9F => todo!(), //Sprite attribute table (OAM)
-            0xFEA0..=0xFEFF => todo!(), //Not Usable
-            0xFF00..=0xFF7F => todo!(), //I/O Registers
-            0xFF80..=0xFFFE => todo!(), //High RAM (HRAM)
-            0xFFFF => todo!(),          //Interrupt Enable register (IE)
-        }
+    //one M-cycle passes for the whole system
+    fn tick(&mut self) {
+        self.bus.tick();
+        self.cycles_passed += M;
     }
 
-    fn read_next_u8(&mut self) -> u8 {
-        let next_byte = self.read_byte(self.registers.pc);
-        self.registers.pc += 1;
-        next_byte
+    //the access lands at the end of its M-cycle, after the rest of the system has been ticked
+    fn read_byte(&mut self, addr: u16) -> u8 {
+        self.tick();
+        self.bus.read_byte(addr)
     }
 
-    fn read_next_u16(&mut self) -> u16 {
-        let low_bytes = self.read_next_u8() as u16;
-        let high_bytes = self.read_next_u8() as u16;
-        (high_bytes << 8) | low_bytes
+    //reads the instruction at PC through the decoder, one M-cycle per byte, and moves PC past it.
+    //returns the opcode too, the decoded instruction doesn't keep it
+    fn fetch(&mut self) -> (u8, Instruction) {
+        let pc = self.registers.pc;
+        //the HALT bug reads the opcode without moving PC, so its first operand byte is the opcode
+        //again. decoding from one byte earlier gets the addresses and JR targets right
+        let addr = if std::mem::take(&mut self.halt_bug) {
+            pc.wrapping_sub(1)
+        } else {
+            pc
+        };
+
+        let mut opcode = 0;
+        let inst = Instruction::decode_with(addr, |at| {
+            if at == addr {
+                opcode = self.read_byte(pc);
+                opcode
+            } else if opcode == 0x10 {
+                //the byte after STOP is skipped without being read
+                self.bus.peek_byte(at)
+            } else {
+                self.read_byte(at)
+            }
+        });
+        self.registers.pc = inst.next_addr();
+        (opcode, inst)
     }
 
-    fn write_byte(&mut self, addr: u16, data: u8) {
-        match addr {
-            0x0000..=0x3FFF => todo!(), //16 KiB ROM bank 00
-            0x4000..=0x7FFF => todo!(), //16 KiB ROM Bank 01~NN
-            0x8000..=0x9FFF => todo!(), //8 KiB Video RAM (VRAM)
-            0xA000..=0xBFFF => todo!(), //8 KiB External RAM
-            0xC000..=0xCFFF => todo!(), //4 KiB Work RAM (WRAM)
-            0xD000..=0xDFFF => todo!(), //4 KiB Work RAM (WRAM)
-            0xE000..=0xFDFF => todo!(), //Mirror of C000~DDFF (ECHO RAM)
-            0xFE00..=0xFE9F => todo!(), //Sprite attribute table (OAM)
-            0xFEA0..=0xFEFF => todo!(), //Not Usable
-            0xFF00..=0xFF7F => todo!(), //I/O Registers
-            0xFF80..=0xFFFE => todo!(), //High RAM (HRAM)
-            0xFFFF => todo!(),          //Interrupt Enable register (IE)
+    fn imm8(&self) -> Result<u8, CpuError> {
+        match self.operand {
+            Some(Operand::U8(data)) => Ok(data),
+            Some(Operand::I8(data)) => Ok(data as u8),
+            Some(Operand::HighAddr(addr)) => Ok(addr as u8),
+            _ => Err(CpuError::BadArgument),
+        }
+    }
+
+    fn imm16(&self) -> Result<u16, CpuError> {
+        match self.operand {
+            Some(Operand::U16(data)) => Ok(data),
+            _ => Err(CpuError::BadArgument),
         }
     }
 
+    fn write_byte(&mut self, addr: u16, data: u8) {
+        self.tick();
+        self.bus.write_byte(addr, data);
+    }
+
+    //PUSH, CALL and RST all spend one internal M-cycle decrementing SP before the first write
     fn push(&mut self, value: u16) {
         let [msb, lsb] = value.to_be_bytes();
 
-        self.registers.sp.wrapping_sub(1);
+        self.tick();
+        self.registers.sp = self.registers.sp.wrapping_sub(1);
         self.write_byte(self.registers.sp, msb);
-        self.registers.sp.wrapping_sub(1);
+        self.registers.sp = self.registers.sp.wrapping_sub(1);
         self.write_byte(self.registers.sp, lsb);
     }
 
     fn pop(&mut self) -> u16 {
         let lsb = self.read_byte(self.registers.sp);
-        self.registers.sp.wrapping_add(1);
+        self.registers.sp = self.registers.sp.wrapping_add(1);
         let msb = self.read_byte(self.registers.sp);
-        self.registers.sp.wrapping_add(1);
+        self.registers.sp = self.registers.sp.wrapping_add(1);
 
         u16::from_be_bytes([msb, lsb])
     }
 
-    pub fn exec_next(&mut self) {
+    fn pending_interrupts(&mut self) -> u8 {
+        //peeked without ticking, the CPU checks these in parallel with everything else
+        self.bus.peek_byte(0xFFFF) & self.bus.peek_byte(0xFF0F) & 0b0001_1111
+    }
+
+    fn dispatch_interrupt(&mut self, pending: u8) {
+        let interrupt = pending.trailing_zeros() as usize;
+        let interrupt_flag = self.bus.peek_byte(0xFF0F);
+        self.bus
+            .write_byte(0xFF0F, interrupt_flag & !(1 << interrupt));
+
+        self.ime = false;
+        self.tick();
+        self.push(self.registers.pc);
+        self.tick();
+        self.jump(INTERRUPT_VECTORS[interrupt]);
+    }
+
+    /// Runs the CPU for one step: dispatches a pending interrupt, waits one M-cycle while halted,
+    /// or executes one instruction.
+    pub fn step(&mut self) -> Result<StepInfo, CpuError> {
+        let cycles_before = self.cycles_passed;
+        let pc = self.registers.pc;
+        self.breakpoint = false;
+
+        if let Some(opcode) = self.locked {
+            //the rest of the system keeps running while the CPU hangs
+            self.tick();
+            return Err(CpuError::LockedUp(opcode));
+        }
+
+        let pending = self.pending_interrupts();
+
+        if self.stopped {
+            //the system clock is stopped too, so the rest of the system is not ticked. Only a
+            //button press ends STOP, whatever IE says
+            self.cycles_passed += M;
+            if self.bus.peek_byte(0xFF0F).get_bit(INT_JOYPAD) {
+                self.stopped = false;
+            }
+        } else if self.halted {
+            self.tick();
+            if pending != 0 {
+                self.halted = false;
+            }
+        } else if self.ime && pending != 0 {
+            self.dispatch_interrupt(pending);
+        } else {
+            if self.trace.is_some() {
+                let line = self.trace_line();
+                if let Some(trace) = &mut self.trace {
+                    writeln!(trace, "{}", line).map_err(|err| CpuError::TraceFailed(err.kind()))?;
+                }
+            }
+
+            let enable_ime = self.ime_scheduled;
+            self.exec_next()?;
+            if enable_ime && self.ime_scheduled {
+                self.ime = true;
+                self.ime_scheduled = false;
+            }
+        }
+
+        Ok(StepInfo {
+            pc,
+            cycles: self.cycles_passed - cycles_before,
+            breakpoint: self.breakpoint,
+        })
+    }
+
+    fn exec_next(&mut self) -> Result<(), CpuError> {
         use opcode::{Op16, Op8, OpcodePrefixed, OpcodeUnprefixed};
 
-        let opcode = self.read_next_u8();
-        let opcode_info = &opcode::LOOKUP[opcode as usize];
-        // self.cycles_passed += opcode_info.cycles;
+        let (opcode, inst) = self.fetch();
+        self.operand = inst.operand;
+        let opcode_info = match inst.info {
+            OpInfo::Unprefixed(info) => info,
+            OpInfo::Prefixed(info) => return self.exec_prefixed(info.inst),
+        };
 
         match opcode_info.inst {
             OpcodeUnprefixed::Nop => {}
             OpcodeUnprefixed::Ld16(target, source) => {
                 let val = match source {
-                    Op16::U16 => self.read_next_u16(),
-                    Op16::HL => self.registers.hl.as_both(),
+                    Op16::U16 => self.imm16()?,
+                    Op16::HL => {
+                        self.tick();
+                        self.registers.hl.as_both()
+                    }
                     Op16::SPPlusI8 => {
-                        let signed_imm = self.read_next_u8();
-                        self.registers.sp.wrapping_add(signed_imm as u16)
+                        let signed_imm = self.imm8()?;
+                        self.tick();
+                        self.add_sp_signed(signed_imm)
                     }
-                    _ => unimplemented!(),
+                    _ => return Err(CpuError::BadArgument),
                 };
 
                 match target {
@@ -263,137 +535,54 @@ impl Cpu {
                     Op16::BC => self.registers.bc.set_both(val),
                     Op16::DE => self.registers.de.set_both(val),
                     Op16::HL => self.registers.hl.set_both(val),
-                    _ => unimplemented!(),
+                    _ => return Err(CpuError::BadArgument),
                 };
             }
+            OpcodeUnprefixed::Ld8(Op8::B, Op8::B) if self.test_mode => self.breakpoint = true,
             OpcodeUnprefixed::Ld8(target, source) => {
-                let data = match source {
-                    Op8::U8 => self.read_next_u8(),
-                    Op8::AddrU16 => {
-                        let addr = self.read_next_u16();
-                        self.read_byte(addr)
-                    }
-                    Op8::A => self.registers.a,
-                    Op8::B => self.registers.bc.hi,
-                    Op8::C => self.registers.bc.lo,
-                    Op8::D => self.registers.de.hi,
-                    Op8::E => self.registers.de.lo,
-                    Op8::H => self.registers.hl.hi,
-                    Op8::L => self.registers.hl.lo,
-                    Op8::AddrBC => self.read_byte(self.registers.bc.as_both()),
-                    Op8::AddrDE => self.read_byte(self.registers.de.as_both()),
-                    Op8::AddrHL => self.read_byte(self.registers.hl.as_both()),
-                    Op8::AddrHLInc | Op8::AddrHLDec => {
-                        let val = self.read_byte(self.registers.hl.as_both());
-                        if source == Op8::AddrHLInc {
-                            self.registers.hl.inc();
-                        } else {
-                            self.registers.hl.dec();
-                        }
-                        val
-                    }
-                    Op8::LowAddrC | Op8::LowAddrU8 => {
-                        let low_bits = if source == Op8::LowAddrC {
-                            self.registers.bc.lo
-                        } else {
-                            self.read_next_u8()
-                        };
-                        let addr = (low_bits as u16) + 0xFF00;
-                        self.read_byte(addr)
-                    }
-                    _ => unimplemented!(),
-                };
-
-                match target {
-                    Op8::A => self.registers.a = data,
-                    Op8::B => self.registers.bc.hi = data,
-                    Op8::C => self.registers.bc.lo = data,
-                    Op8::D => self.registers.de.hi = data,
-                    Op8::E => self.registers.de.lo = data,
-                    Op8::H => self.registers.hl.hi = data,
-                    Op8::L => self.registers.hl.lo = data,
-                    Op8::AddrBC => self.write_byte(self.registers.bc.as_both(), data),
-                    Op8::AddrDE => self.write_byte(self.registers.de.as_both(), data),
-                    Op8::AddrHL => self.write_byte(self.registers.hl.as_both(), data),
-                    Op8::AddrHLInc | Op8::AddrHLDec => {
-                        self.write_byte(self.registers.hl.as_both(), data);
-                        if target == Op8::AddrHLInc {
-                            self.registers.hl.inc();
-                        } else {
-                            self.registers.hl.dec();
-                        }
-                    }
-                    Op8::AddrU16 => {
-                        let addr = self.read_next_u16();
-                        self.write_byte(addr, data);
-                    }
-                    Op8::LowAddrC | Op8::LowAddrU8 => {
-                        let low_bits = if source == Op8::LowAddrC {
-                            self.registers.bc.lo
-                        } else {
-                            self.read_next_u8()
-                        };
-                        let addr = (low_bits as u16) + 0xFF00;
-                        self.write_byte(addr, data);
-                    }
-                    _ => unimplemented!(),
-                };
+                let data = self.read_op8(source)?;
+                self.write_op8(target, data)?;
             }
             OpcodeUnprefixed::LdU16StackAddr => {
-                let addr = self.read_next_u16();
-                let data = self.read_byte(addr);
-                self.write_byte(addr, data);
+                let addr = self.imm16()?;
+                let [msb, lsb] = self.registers.sp.to_be_bytes();
+                self.write_byte(addr, lsb);
+                self.write_byte(addr.wrapping_add(1), msb);
             }
             OpcodeUnprefixed::Inc8(target) => {
-                match target {
-                    Op8::A => self.registers.a = self.registers.a.wrapping_add(1),
-                    Op8::B => self.registers.bc.hi = self.registers.bc.hi.wrapping_add(1),
-                    Op8::C => self.registers.bc.lo = self.registers.bc.lo.wrapping_add(1),
-                    Op8::D => self.registers.de.hi = self.registers.de.hi.wrapping_add(1),
-                    Op8::E => self.registers.de.lo = self.registers.de.lo.wrapping_add(1),
-                    Op8::H => self.registers.hl.hi = self.registers.hl.hi.wrapping_add(1),
-                    Op8::L => self.registers.hl.lo = self.registers.hl.lo.wrapping_add(1),
-                    Op8::AddrHL => {
-                        let addr = self.registers.hl.as_both();
-                        let data = self.read_byte(addr).wrapping_add(1);
-                        self.write_byte(addr, data);
-                    }
-                    _ => unimplemented!(),
-                };
+                let data = self.read_op8(target)?;
+                let result = data.wrapping_add(1);
+                self.registers.flags.zero = result == 0;
+                self.registers.flags.neg = false;
+                self.registers.flags.half_carry = data & 0x0F == 0x0F;
+                self.write_op8(target, result)?;
             }
             OpcodeUnprefixed::Inc16(target) => {
+                self.tick();
                 match target {
                     Op16::SP => self.registers.sp = self.registers.sp.wrapping_add(1),
                     Op16::BC => self.registers.bc.inc(),
                     Op16::DE => self.registers.de.inc(),
                     Op16::HL => self.registers.hl.inc(),
-                    _ => unimplemented!(),
+                    _ => return Err(CpuError::BadArgument),
                 };
             }
             OpcodeUnprefixed::Dec8(target) => {
-                match target {
-                    Op8::A => self.registers.a = self.registers.a.wrapping_sub(1),
-                    Op8::B => self.registers.bc.hi = self.registers.bc.hi.wrapping_sub(1),
-                    Op8::C => self.registers.bc.lo = self.registers.bc.lo.wrapping_sub(1),
-                    Op8::D => self.registers.de.hi = self.registers.de.hi.wrapping_sub(1),
-                    Op8::E => self.registers.de.lo = self.registers.de.lo.wrapping_sub(1),
-                    Op8::H => self.registers.hl.hi = self.registers.hl.hi.wrapping_sub(1),
-                    Op8::L => self.registers.hl.lo = self.registers.hl.lo.wrapping_sub(1),
-                    Op8::AddrHL => {
-                        let subr = self.registers.hl.as_both();
-                        let data = self.read_byte(subr).wrapping_sub(1);
-                        self.write_byte(subr, data);
-                    }
-                    _ => unimplemented!(),
-                };
+                let data = self.read_op8(target)?;
+                let result = data.wrapping_sub(1);
+                self.registers.flags.zero = result == 0;
+                self.registers.flags.neg = true;
+                self.registers.flags.half_carry = data & 0x0F == 0x00;
+                self.write_op8(target, result)?;
             }
             OpcodeUnprefixed::Dec16(target) => {
+                self.tick();
                 match target {
                     Op16::SP => self.registers.sp = self.registers.sp.wrapping_sub(1),
                     Op16::BC => self.registers.bc.dec(),
                     Op16::DE => self.registers.de.dec(),
                     Op16::HL => self.registers.hl.dec(),
-                    _ => unimplemented!(),
+                    _ => return Err(CpuError::BadArgument),
                 };
             }
             OpcodeUnprefixed::Add16(rhs) => {
@@ -402,164 +591,105 @@ impl Cpu {
                     Op16::BC => self.registers.bc.as_both(),
                     Op16::DE => self.registers.de.as_both(),
                     Op16::HL => self.registers.hl.as_both(),
-                    _ => unimplemented!(),
+                    _ => return Err(CpuError::BadArgument),
                 };
-                self.registers.hl += data;
+                self.tick();
+                let hl = self.registers.hl.as_both();
+                let (sum, carry) = hl.overflowing_add(data);
+                self.registers.flags.neg = false;
+                self.registers.flags.half_carry = (hl & 0x0FFF) + (data & 0x0FFF) > 0x0FFF;
+                self.registers.flags.carry = carry;
+                self.registers.hl.set_both(sum);
             }
             OpcodeUnprefixed::Add8(rhs) => {
-                let data = match rhs {
-                    Op8::A => self.registers.a,
-                    Op8::B => self.registers.bc.hi,
-                    Op8::C => self.registers.bc.lo,
-                    Op8::D => self.registers.de.hi,
-                    Op8::E => self.registers.de.lo,
-                    Op8::H => self.registers.hl.hi,
-                    Op8::L => self.registers.hl.lo,
-                    Op8::AddrHL => self.read_byte(self.registers.hl.as_both()),
-                    Op8::U8 => self.read_next_u8(),
-                    _ => unimplemented!(),
-                };
-                self.registers.a.wrapping_add(data);
+                let data = self.read_op8(rhs)?;
+                self.add_a(data, false);
             }
             OpcodeUnprefixed::AddI8SP => {
-                let signed = self.read_next_u8() as u16;
-                self.registers.sp.wrapping_add(signed);
+                let signed = self.imm8()?;
+                self.tick();
+                self.tick();
+                self.registers.sp = self.add_sp_signed(signed);
+            }
+            OpcodeUnprefixed::Stop => {
+                //DIV is reset as the clock stops
+                self.bus.write_byte(0xFF04, 0);
+                self.stopped = true;
             }
-            OpcodeUnprefixed::Stop => self.stopped = true,
 
             OpcodeUnprefixed::Jr(cond) => {
-                let cond_satisfied = match cond {
-                    opcode::Condition::Zero => self.registers.flags.zero,
-                    opcode::Condition::NotZero => !self.registers.flags.zero,
-                    opcode::Condition::Carry => self.registers.flags.carry,
-                    opcode::Condition::NotCarry => !self.registers.flags.carry,
-                    opcode::Condition::Unconditional => true,
+                let target = match self.operand {
+                    Some(Operand::Relative(target)) => target,
+                    _ => return Err(CpuError::BadArgument),
                 };
-                let rel_addr = self.read_next_u8() as u16;
-                if cond_satisfied {
-                    self.registers.pc.wrapping_add(rel_addr);
+                if self.test_condition(cond) {
+                    self.tick();
+                    self.registers.pc = target;
                 }
             }
             OpcodeUnprefixed::Daa => self.registers.decimal_adjust_a(),
             OpcodeUnprefixed::Cpl => {
                 self.registers.a = self.registers.a.not();
+                self.registers.flags.neg = true;
+                self.registers.flags.half_carry = true;
             }
             OpcodeUnprefixed::Scf => {
+                self.registers.flags.neg = false;
+                self.registers.flags.half_carry = false;
                 self.registers.flags.carry = true;
             }
             OpcodeUnprefixed::Ccf => {
+                self.registers.flags.neg = false;
+                self.registers.flags.half_carry = false;
                 self.registers.flags.carry = self.registers.flags.carry.not();
             }
-            OpcodeUnprefixed::Halt => todo!(),
+            OpcodeUnprefixed::Halt => {
+                if !self.ime && self.pending_interrupts() != 0 {
+                    self.halt_bug = true;
+                } else {
+                    self.halted = true;
+                }
+            }
             OpcodeUnprefixed::Adc(rhs) => {
-                let data = match rhs {
-                    Op8::A => self.registers.a,
-                    Op8::B => self.registers.bc.hi,
-                    Op8::C => self.registers.bc.lo,
-                    Op8::D => self.registers.de.hi,
-                    Op8::E => self.registers.de.lo,
-                    Op8::H => self.registers.hl.hi,
-                    Op8::L => self.registers.hl.lo,
-                    Op8::AddrHL => self.read_byte(self.registers.hl.as_both()),
-                    Op8::U8 => self.read_next_u8(),
-                    _ => unimplemented!(),
-                };
-                self.registers.a.wrapping_add(data).wrapping_add(1);
+                let data = self.read_op8(rhs)?;
+                self.add_a(data, self.registers.flags.carry);
             }
             OpcodeUnprefixed::Sub(rhs) => {
-                let data = match rhs {
-                    Op8::A => self.registers.a,
-                    Op8::B => self.registers.bc.hi,
-                    Op8::C => self.registers.bc.lo,
-                    Op8::D => self.registers.de.hi,
-                    Op8::E => self.registers.de.lo,
-                    Op8::H => self.registers.hl.hi,
-                    Op8::L => self.registers.hl.lo,
-                    Op8::AddrHL => self.read_byte(self.registers.hl.as_both()),
-                    Op8::U8 => self.read_next_u8(),
-                    _ => unimplemented!(),
-                };
-
-                self.registers.a.wrapping_sub(data);
+                let data = self.read_op8(rhs)?;
+                self.registers.a = self.sub_a(data, false);
             }
             OpcodeUnprefixed::Sbc(rhs) => {
-                let data = match rhs {
-                    Op8::A => self.registers.a,
-                    Op8::B => self.registers.bc.hi,
-                    Op8::C => self.registers.bc.lo,
-                    Op8::D => self.registers.de.hi,
-                    Op8::E => self.registers.de.lo,
-                    Op8::H => self.registers.hl.hi,
-                    Op8::L => self.registers.hl.lo,
-                    Op8::AddrHL => self.read_byte(self.registers.hl.as_both()),
-                    Op8::U8 => self.read_next_u8(),
-                    _ => unimplemented!(),
-                };
-                let carry_bit = self.registers.flags.carry as u8;
-                self.registers.a = self.registers.a.wrapping_sub(data).wrapping_sub(carry_bit);
+                let data = self.read_op8(rhs)?;
+                self.registers.a = self.sub_a(data, self.registers.flags.carry);
             }
             OpcodeUnprefixed::And(rhs) => {
-                let data = match rhs {
-                    Op8::A => self.registers.a,
-                    Op8::B => self.registers.bc.hi,
-                    Op8::C => self.registers.bc.lo,
-                    Op8::D => self.registers.de.hi,
-                    Op8::E => self.registers.de.lo,
-                    Op8::H => self.registers.hl.hi,
-                    Op8::L => self.registers.hl.lo,
-                    Op8::AddrHL => self.read_byte(self.registers.hl.as_both()),
-                    Op8::U8 => self.read_next_u8(),
-                    _ => unimplemented!(),
-                };
+                let data = self.read_op8(rhs)?;
                 self.registers.a &= data;
+                self.set_logic_flags(true);
             }
             OpcodeUnprefixed::Xor(rhs) => {
-                let data = match rhs {
-                    Op8::A => self.registers.a,
-                    Op8::B => self.registers.bc.hi,
-                    Op8::C => self.registers.bc.lo,
-                    Op8::D => self.registers.de.hi,
-                    Op8::E => self.registers.de.lo,
-                    Op8::H => self.registers.hl.hi,
-                    Op8::L => self.registers.hl.lo,
-                    Op8::AddrHL => self.read_byte(self.registers.hl.as_both()),
-                    Op8::U8 => self.read_next_u8(),
-                    _ => unimplemented!(),
-                };
+                let data = self.read_op8(rhs)?;
                 self.registers.a ^= data;
+                self.set_logic_flags(false);
             }
             OpcodeUnprefixed::Or(rhs) => {
-                let data = match rhs {
-                    Op8::A => self.registers.a,
-                    Op8::B => self.registers.bc.hi,
-                    Op8::C => self.registers.bc.lo,
-                    Op8::D => self.registers.de.hi,
-                    Op8::E => self.registers.de.lo,
-                    Op8::H => self.registers.hl.hi,
-                    Op8::L => self.registers.hl.lo,
-                    Op8::AddrHL => self.read_byte(self.registers.hl.as_both()),
-                    Op8::U8 => self.read_next_u8(),
-                    _ => unimplemented!(),
-                };
+                let data = self.read_op8(rhs)?;
                 self.registers.a |= data;
+                self.set_logic_flags(false);
             }
             OpcodeUnprefixed::Cp(rhs) => {
-                let data = match rhs {
-                    Op8::A => self.registers.a,
-                    Op8::B => self.registers.bc.hi,
-                    Op8::C => self.registers.bc.lo,
-                    Op8::D => self.registers.de.hi,
-                    Op8::E => self.registers.de.lo,
-                    Op8::H => self.registers.hl.hi,
-                    Op8::L => self.registers.hl.lo,
-                    Op8::AddrHL => self.read_byte(self.registers.hl.as_both()),
-                    Op8::U8 => self.read_next_u8(),
-                    _ => unimplemented!(),
-                };
+                let data = self.read_op8(rhs)?;
+                self.sub_a(data, false);
             }
             OpcodeUnprefixed::Ret(cond) => {
+                //checking the condition costs an extra M-cycle
+                if cond != opcode::Condition::Unconditional {
+                    self.tick();
+                }
                 if self.test_condition(cond) {
-                    self.registers.sp = self.pop();
+                    let ret_addr = self.pop();
+                    self.tick();
+                    self.jump(ret_addr);
                 }
             }
             OpcodeUnprefixed::Pop(target) => {
@@ -569,343 +699,285 @@ impl Cpu {
                     Op16::DE => self.registers.de.set_both(value),
                     Op16::HL => self.registers.hl.set_both(value),
                     Op16::AF => self.registers.set_af(value),
-                    _ => unimplemented!(),
+                    _ => return Err(CpuError::BadArgument),
                 }
             }
             OpcodeUnprefixed::Jp(cond, source) => {
                 let jump_addr = match source {
-                    Op16::U16 => self.read_next_u16(),
+                    Op16::U16 => self.imm16()?,
                     Op16::HL => self.registers.hl.as_both(),
-                    _ => unimplemented!(),
+                    _ => return Err(CpuError::BadArgument),
                 };
 
                 if self.test_condition(cond) {
+                    if source == Op16::U16 {
+                        self.tick();
+                    }
                     self.jump(jump_addr);
                 }
             }
             OpcodeUnprefixed::Call(cond) => {
-                let jump_addr = self.read_next_u16();
+                let jump_addr = self.imm16()?;
                 if self.test_condition(cond) {
                     self.push(self.registers.pc);
                     self.jump(jump_addr);
                 }
             }
             OpcodeUnprefixed::Push(target) => {
-                let value = self.pop();
+                let value = match target {
+                    Op16::BC => self.registers.bc.as_both(),
+                    Op16::DE => self.registers.de.as_both(),
+                    Op16::HL => self.registers.hl.as_both(),
+                    Op16::AF => self.registers.af(),
+                    _ => return Err(CpuError::BadArgument),
+                };
 
-                match target {
-                    Op16::BC => self.registers.bc.set_both(value),
-                    Op16::DE => self.registers.de.set_both(value),
-                    Op16::HL => self.registers.hl.set_both(value),
-                    Op16::AF => self.registers.set_af(value),
-                    _ => unimplemented!(),
-                }
+                self.push(value);
             }
             OpcodeUnprefixed::Rst(interrupt_addr) => {
                 self.push(self.registers.pc);
                 self.jump(interrupt_addr);
             }
-            OpcodeUnprefixed::Prefix => {
-                let opcode = self.read_next_u8();
-                let opcode_info = &opcode::SECONDARY[opcode as usize];
-                match opcode_info.inst {
-                    OpcodePrefixed::Swap(target) => match target {
-                        Op8::A => self.registers.a.swap_nibbles(),
-                        Op8::B => self.registers.bc.hi.swap_nibbles(),
-                        Op8::C => self.registers.bc.lo.swap_nibbles(),
-                        Op8::D => self.registers.de.hi.swap_nibbles(),
-                        Op8::E => self.registers.de.lo.swap_nibbles(),
-                        Op8::H => self.registers.hl.hi.swap_nibbles(),
-                        Op8::L => self.registers.hl.lo.swap_nibbles(),
-                        Op8::AddrHL => {
-                            let addr = self.registers.hl.as_both();
-                            let mut data = self.read_byte(addr);
-                            data.swap_nibbles();
-                            self.write_byte(addr, data);
-                        }
-                        _ => unimplemented!(),
-                    },
-                    OpcodePrefixed::Sra(target) => match target {
-                        Op8::A => self.registers.a = self.registers.a.rotate_right(1),
-                        Op8::B => self.registers.bc.hi = self.registers.bc.hi.rotate_right(1),
-                        Op8::C => self.registers.bc.lo = self.registers.bc.lo.rotate_right(1),
-                        Op8::D => self.registers.de.hi = self.registers.de.hi.rotate_right(1),
-                        Op8::E => self.registers.de.lo = self.registers.de.lo.rotate_right(1),
-                        Op8::H => self.registers.hl.hi = self.registers.hl.hi.rotate_right(1),
-                        Op8::L => self.registers.hl.lo = self.registers.hl.lo.rotate_right(1),
-                        Op8::AddrHL => {
-                            let addr = self.registers.hl.as_both();
-                            let data = self.read_byte(addr).rotate_right(1);
-                            self.write_byte(addr, data);
-                        }
-                        _ => unimplemented!(),
-                    },
-                    OpcodePrefixed::Sla(target) => match target {
-                        Op8::A => self.registers.a = self.registers.a.rotate_left(1),
-                        Op8::B => self.registers.bc.hi = self.registers.bc.hi.rotate_left(1),
-                        Op8::C => self.registers.bc.lo = self.registers.bc.lo.rotate_left(1),
-                        Op8::D => self.registers.de.hi = self.registers.de.hi.rotate_left(1),
-                        Op8::E => self.registers.de.lo = self.registers.de.lo.rotate_left(1),
-                        Op8::H => self.registers.hl.hi = self.registers.hl.hi.rotate_left(1),
-                        Op8::L => self.registers.hl.lo = self.registers.hl.lo.rotate_left(1),
-                        Op8::AddrHL => {
-                            let addr = self.registers.hl.as_both();
-                            let data = self.read_byte(addr).rotate_left(1);
-                            self.write_byte(addr, data);
-                        }
-                        _ => unimplemented!(),
-                    },
-                    OpcodePrefixed::Bit(bit, target) => {
-                        let test = match target {
-                            Op8::A => self.registers.a.get_bit(bit),
-                            Op8::B => self.registers.bc.hi.get_bit(bit),
-                            Op8::C => self.registers.bc.lo.get_bit(bit),
-                            Op8::D => self.registers.de.hi.get_bit(bit),
-                            Op8::E => self.registers.de.lo.get_bit(bit),
-                            Op8::H => self.registers.hl.hi.get_bit(bit),
-                            Op8::L => self.registers.hl.lo.get_bit(bit),
-                            Op8::AddrHL => {
-                                let addr = self.registers.hl.as_both();
-                                self.read_byte(addr).get_bit(bit)
-                            }
-                            _ => unimplemented!(),
-                        };
-                    }
-                    OpcodePrefixed::Set(bit, target) | OpcodePrefixed::Res(bit, target) => {
-                        //true for SET, false for RES
-                        let val_to_set = matches!(opcode_info.inst, OpcodePrefixed::Set(_, _));
-
-                        match target {
-                            Op8::A => self.registers.a.set_bit(bit, val_to_set),
-                            Op8::B => self.registers.bc.hi.set_bit(bit, val_to_set),
-                            Op8::C => self.registers.bc.lo.set_bit(bit, val_to_set),
-                            Op8::D => self.registers.de.hi.set_bit(bit, val_to_set),
-                            Op8::E => self.registers.de.lo.set_bit(bit, val_to_set),
-                            Op8::H => self.registers.hl.hi.set_bit(bit, val_to_set),
-                            Op8::L => self.registers.hl.lo.set_bit(bit, val_to_set),
-                            Op8::AddrHL => {
-                                let addr = self.registers.hl.as_both();
-                                let mut data = self.read_byte(addr);
-                                data.set_bit(bit, val_to_set);
-                                self.write_byte(addr, data);
-                            }
-                            _ => unimplemented!(),
-                        }
-                    }
-                    OpcodePrefixed::Rl(target) => {
-                        let mut new_carry;
-                        match target {
-                            Op8::A => {
-                                new_carry = self.registers.a.shr(7u8).bitor(1);
-                                self.registers.a = self.registers.a.shl(1u8).bitor(new_carry);
-                            }
-                            Op8::B => {
-                                new_carry = self.registers.bc.hi.shr(7u8).bitor(1);
-                                self.registers.bc.hi =
-                                    self.registers.bc.hi.shl(1u8).bitor(new_carry);
-                            }
-                            Op8::C => {
-                                new_carry = self.registers.bc.lo.shr(7u8).bitor(1);
-                                self.registers.bc.lo =
-                                    self.registers.bc.lo.shl(1u8).bitor(new_carry);
-                            }
-                            Op8::D => {
-                                new_carry = self.registers.de.hi.shr(7u8).bitor(1);
-                                self.registers.de.hi =
-                                    self.registers.de.hi.shl(1u8).bitor(new_carry);
-                            }
-                            Op8::E => {
-                                new_carry = self.registers.de.lo.shr(7u8).bitor(1);
-                                self.registers.de.lo =
-                                    self.registers.de.lo.shl(1u8).bitor(new_carry);
-                            }
-                            Op8::H => {
-                                new_carry = self.registers.hl.hi.shr(7u8).bitor(1);
-                                self.registers.hl.hi =
-                                    self.registers.hl.hi.shl(1u8).bitor(new_carry);
-                            }
-                            Op8::L => {
-                                new_carry = self.registers.hl.lo.shr(7u8).bitand(1);
-                                self.registers.hl.lo =
-                                    self.registers.hl.lo.shl(1u8).bitor(new_carry);
-                            }
-                            Op8::AddrHL => {
-                                let addr = self.registers.hl.as_both();
-                                let mut data = self.read_byte(addr);
-                                new_carry = data.shr(7u8).bitor(1);
-                                data = data.shl(1u8).bitor(new_carry);
-                                self.write_byte(addr, data);
-                            }
-                            _ => unimplemented!(),
-                        }
-
-                        self.registers.flags.carry = new_carry == 1;
-                    }
-                    OpcodePrefixed::Rr(target) => {
-                        let mut new_carry;
-                        match target {
-                            Op8::A => {
-                                new_carry = self.registers.a.bitand(1);
-                                self.registers.a = self.registers.a.shr(1u8).bitor(new_carry);
-                            }
-                            Op8::B => {
-                                new_carry = self.registers.bc.hi.bitand(1);
-                                self.registers.bc.hi =
-                                    self.registers.bc.hi.shr(1u8).bitor(new_carry);
-                            }
-                            Op8::C => {
-                                new_carry = self.registers.bc.lo.bitand(1);
-                                self.registers.bc.lo =
-                                    self.registers.bc.lo.shr(1u8).bitor(new_carry);
-                            }
-                            Op8::D => {
-                                new_carry = self.registers.de.hi.bitand(1);
-                                self.registers.de.hi =
-                                    self.registers.de.hi.shr(1u8).bitor(new_carry);
-                            }
-                            Op8::E => {
-                                new_carry = self.registers.de.lo.bitand(1);
-                                self.registers.de.lo =
-                                    self.registers.de.lo.shr(1u8).bitor(new_carry);
-                            }
-                            Op8::H => {
-                                new_carry = self.registers.hl.hi.bitand(1);
-                                self.registers.hl.hi =
-                                    self.registers.hl.hi.shr(1u8).bitor(new_carry);
-                            }
-                            Op8::L => {
-                                new_carry = self.registers.hl.lo.bitand(1);
-                                self.registers.hl.lo =
-                                    self.registers.hl.lo.shr(1u8).bitor(new_carry);
-                            }
-                            Op8::AddrHL => {
-                                let addr = self.registers.hl.as_both();
-                                let mut data = self.read_byte(addr);
-                                new_carry = data.bitand(1);
-                                data = data.shr(1u8).bitor(new_carry);
-                                self.write_byte(addr, data);
-                            }
-                            _ => unimplemented!(),
-                        }
-
-                        self.registers.flags.carry = new_carry == 1;
-                    }
-                    OpcodePrefixed::Rlc(target) => match target {
-                        Op8::A => {
-                            self.registers.flags.carry = self.registers.a.shr(7u8) == 1;
-                            self.registers.a = self.registers.a.rotate_left(1);
-                        }
-                        Op8::B => {
-                            self.registers.flags.carry = self.registers.bc.hi.shr(7u8) == 1;
-                            self.registers.bc.hi = self.registers.bc.hi.rotate_left(1);
-                        }
-                        Op8::C => {
-                            self.registers.flags.carry = self.registers.bc.lo.shr(7u8) == 1;
-                            self.registers.bc.lo = self.registers.bc.lo.rotate_left(1);
-                        }
-                        Op8::D => {
-                            self.registers.flags.carry = self.registers.de.hi.shr(7u8) == 1;
-                            self.registers.de.hi = self.registers.de.hi.rotate_left(1);
-                        }
-                        Op8::E => {
-                            self.registers.flags.carry = self.registers.de.lo.shr(7u8) == 1;
-                            self.registers.de.lo = self.registers.de.lo.rotate_left(1);
-                        }
-                        Op8::H => {
-                            self.registers.flags.carry = self.registers.hl.hi.shr(7u8) == 1;
-                            self.registers.hl.hi = self.registers.hl.hi.rotate_left(1);
-                        }
-                        Op8::L => {
-                            self.registers.flags.carry = self.registers.hl.lo.shr(7u8) == 1;
-                            self.registers.hl.lo = self.registers.hl.lo.rotate_left(1);
-                        }
-                        Op8::AddrHL => {
-                            let addr = self.registers.hl.as_both();
-                            let mut data = self.read_byte(addr);
-                            self.registers.flags.carry = data.shr(7u8) == 1;
-                            data = data.rotate_left(1);
-                            self.write_byte(addr, data);
-                        }
-                        _ => unimplemented!(),
-                    },
-                    OpcodePrefixed::Rrc(target) => match target {
-                        Op8::A => {
-                            self.registers.flags.carry = self.registers.a.bitand(1) == 1;
-                            self.registers.a = self.registers.a.rotate_right(1);
-                        }
-                        Op8::B => {
-                            self.registers.flags.carry = self.registers.bc.hi.bitand(1) == 1;
-                            self.registers.bc.hi = self.registers.bc.hi.rotate_right(1);
-                        }
-                        Op8::C => {
-                            self.registers.flags.carry = self.registers.bc.lo.bitand(1) == 1;
-                            self.registers.bc.lo = self.registers.bc.lo.rotate_right(1);
-                        }
-                        Op8::D => {
-                            self.registers.flags.carry = self.registers.de.hi.bitand(1) == 1;
-                            self.registers.de.hi = self.registers.de.hi.rotate_right(1);
-                        }
-                        Op8::E => {
-                            self.registers.flags.carry = self.registers.de.lo.bitand(1) == 1;
-                            self.registers.de.lo = self.registers.de.lo.rotate_right(1);
-                        }
-                        Op8::H => {
-                            self.registers.flags.carry = self.registers.hl.hi.bitand(1) == 1;
-                            self.registers.hl.hi = self.registers.hl.hi.rotate_right(1);
-                        }
-                        Op8::L => {
-                            self.registers.flags.carry = self.registers.hl.lo.bitand(1) == 1;
-                            self.registers.hl.lo = self.registers.hl.lo.rotate_right(1);
-                        }
-                        Op8::AddrHL => {
-                            let addr = self.registers.hl.as_both();
-                            let mut data = self.read_byte(addr);
-                            self.registers.flags.carry = data.bitand(1) == 1;
-                            data = data.rotate_right(1);
-                            self.write_byte(addr, data);
-                        }
-                        _ => unimplemented!(),
-                    },
-                    OpcodePrefixed::Srl(target) => match target {
-                        Op8::A => self.registers.a.shr_assign(1),
-                        Op8::B => self.registers.bc.hi.shr_assign(1),
-                        Op8::C => self.registers.bc.lo.shr_assign(1),
-                        Op8::D => self.registers.de.hi.shr_assign(1),
-                        Op8::E => self.registers.de.lo.shr_assign(1),
-                        Op8::H => self.registers.hl.hi.shr_assign(1),
-                        Op8::L => self.registers.hl.lo.shr_assign(1),
-                        Op8::AddrHL => {
-                            let addr = self.registers.hl.as_both();
-                            let data = self.read_byte(addr).shr(1);
-                            self.write_byte(addr, data);
-                        }
-                        _ => unimplemented!(),
-                    },
-                }
+            //the decoder already went past 0xCB
+            OpcodeUnprefixed::Prefix => unreachable!(),
+            OpcodeUnprefixed::Reti => {
+                let ret_addr = self.pop();
+                self.tick();
+                self.jump(ret_addr);
+                self.ime = true;
+            }
+            OpcodeUnprefixed::Di => {
+                self.ime = false;
+                self.ime_scheduled = false;
             }
-            OpcodeUnprefixed::Reti => todo!(),
-            OpcodeUnprefixed::Di => todo!(),
-            OpcodeUnprefixed::Ei => todo!(),
+            OpcodeUnprefixed::Ei => self.ime_scheduled = true,
 
-            OpcodeUnprefixed::Illegal => panic!("got illegal command"),
+            OpcodeUnprefixed::Illegal => {
+                self.locked = Some(opcode);
+                return Err(CpuError::IllegalOpcode(opcode));
+            }
 
+            //unlike their CB-prefixed versions, these always clear Z
             OpcodeUnprefixed::Rlca => {
-                self.registers.flags.carry = self.registers.a.shr(7u8) == 1;
-                self.registers.a = self.registers.a.rotate_left(1);
+                self.shift_op8(Op8::A, |data, _| (data.rotate_left(1), data.get_bit(7)))?;
+                self.registers.flags.zero = false;
             }
             OpcodeUnprefixed::Rrca => {
-                self.registers.flags.carry = self.registers.a.bitand(1) == 1;
-                self.registers.a = self.registers.a.rotate_right(1);
+                self.shift_op8(Op8::A, |data, _| (data.rotate_right(1), data.get_bit(0)))?;
+                self.registers.flags.zero = false;
             }
             OpcodeUnprefixed::Rla => {
-                let mut new_carry = self.registers.a.shr(7u8).bitor(1);
-                self.registers.a = self.registers.a.shl(1u8).bitor(new_carry);
+                self.shift_op8(Op8::A, |data, carry| {
+                    (data.shl(1u8).bitor(carry as u8), data.get_bit(7))
+                })?;
+                self.registers.flags.zero = false;
             }
             OpcodeUnprefixed::Rra => {
-                let mut new_carry = self.registers.a.bitand(1);
-                self.registers.a = self.registers.a.shr(1u8).bitor(new_carry);
+                self.shift_op8(Op8::A, |data, carry| {
+                    (data.shr(1u8).bitor((carry as u8).shl(7u8)), data.get_bit(0))
+                })?;
+                self.registers.flags.zero = false;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn exec_prefixed(&mut self, inst: opcode::OpcodePrefixed) -> Result<(), CpuError> {
+        use opcode::OpcodePrefixed;
+
+        match inst {
+            OpcodePrefixed::Rlc(target) => {
+                self.shift_op8(target, |data, _| (data.rotate_left(1), data.get_bit(7)))?
+            }
+            OpcodePrefixed::Rrc(target) => {
+                self.shift_op8(target, |data, _| (data.rotate_right(1), data.get_bit(0)))?
+            }
+            OpcodePrefixed::Rl(target) => self.shift_op8(target, |data, carry| {
+                (data.shl(1u8).bitor(carry as u8), data.get_bit(7))
+            })?,
+            OpcodePrefixed::Rr(target) => self.shift_op8(target, |data, carry| {
+                (data.shr(1u8).bitor((carry as u8).shl(7u8)), data.get_bit(0))
+            })?,
+            OpcodePrefixed::Sla(target) => {
+                self.shift_op8(target, |data, _| (data.shl(1u8), data.get_bit(7)))?
+            }
+            OpcodePrefixed::Sra(target) => self.shift_op8(target, |data, _| {
+                (
+                    data.shr(1u8).bitor(data.bitand(0b1000_0000)),
+                    data.get_bit(0),
+                )
+            })?,
+            OpcodePrefixed::Srl(target) => {
+                self.shift_op8(target, |data, _| (data.shr(1u8), data.get_bit(0)))?
+            }
+            OpcodePrefixed::Swap(target) => self.shift_op8(target, |mut data, _| {
+                data.swap_nibbles();
+                (data, false)
+            })?,
+            OpcodePrefixed::Bit(bit, target) => {
+                let data = self.read_op8(target)?;
+                self.registers.flags.zero = !data.get_bit(bit);
+                self.registers.flags.neg = false;
+                self.registers.flags.half_carry = true;
+            }
+            OpcodePrefixed::Set(bit, target) | OpcodePrefixed::Res(bit, target) => {
+                //true for SET, false for RES
+                let val_to_set = matches!(inst, OpcodePrefixed::Set(_, _));
+
+                let mut data = self.read_op8(target)?;
+                data.set_bit(bit, val_to_set);
+                self.write_op8(target, data)?;
             }
         }
+
+        Ok(())
+    }
+
+    fn read_op8(&mut self, source: opcode::Op8) -> Result<u8, CpuError> {
+        use opcode::Op8;
+
+        let data = match source {
+            Op8::U8 => self.imm8()?,
+            Op8::AddrU16 => {
+                let addr = self.imm16()?;
+                self.read_byte(addr)
+            }
+            Op8::A => self.registers.a,
+            Op8::B => self.registers.bc.hi,
+            Op8::C => self.registers.bc.lo,
+            Op8::D => self.registers.de.hi,
+            Op8::E => self.registers.de.lo,
+            Op8::H => self.registers.hl.hi,
+            Op8::L => self.registers.hl.lo,
+            Op8::AddrBC => self.read_byte(self.registers.bc.as_both()),
+            Op8::AddrDE => self.read_byte(self.registers.de.as_both()),
+            Op8::AddrHL => self.read_byte(self.registers.hl.as_both()),
+            Op8::AddrHLInc | Op8::AddrHLDec => {
+                let val = self.read_byte(self.registers.hl.as_both());
+                if source == Op8::AddrHLInc {
+                    self.registers.hl.inc();
+                } else {
+                    self.registers.hl.dec();
+                }
+                val
+            }
+            Op8::LowAddrC | Op8::LowAddrU8 => {
+                let low_bits = if source == Op8::LowAddrC {
+                    self.registers.bc.lo
+                } else {
+                    self.imm8()?
+                };
+                let addr = (low_bits as u16) + 0xFF00;
+                self.read_byte(addr)
+            }
+            Op8::I8 => return Err(CpuError::BadArgument),
+        };
+
+        Ok(data)
+    }
+
+    fn write_op8(&mut self, target: opcode::Op8, data: u8) -> Result<(), CpuError> {
+        use opcode::Op8;
+
+        match target {
+            Op8::A => self.registers.a = data,
+            Op8::B => self.registers.bc.hi = data,
+            Op8::C => self.registers.bc.lo = data,
+            Op8::D => self.registers.de.hi = data,
+            Op8::E => self.registers.de.lo = data,
+            Op8::H => self.registers.hl.hi = data,
+            Op8::L => self.registers.hl.lo = data,
+            Op8::AddrBC => self.write_byte(self.registers.bc.as_both(), data),
+            Op8::AddrDE => self.write_byte(self.registers.de.as_both(), data),
+            Op8::AddrHL => self.write_byte(self.registers.hl.as_both(), data),
+            Op8::AddrHLInc | Op8::AddrHLDec => {
+                self.write_byte(self.registers.hl.as_both(), data);
+                if target == Op8::AddrHLInc {
+                    self.registers.hl.inc();
+                } else {
+                    self.registers.hl.dec();
+                }
+            }
+            Op8::AddrU16 => {
+                let addr = self.imm16()?;
+                self.write_byte(addr, data);
+            }
+            Op8::LowAddrC | Op8::LowAddrU8 => {
+                let low_bits = if target == Op8::LowAddrC {
+                    self.registers.bc.lo
+                } else {
+                    self.imm8()?
+                };
+                let addr = (low_bits as u16) + 0xFF00;
+                self.write_byte(addr, data);
+            }
+            Op8::U8 | Op8::I8 => return Err(CpuError::BadArgument),
+        };
+
+        Ok(())
+    }
+
+    //read-modify-write shared by the rotates, shifts and SWAP. `op` gets the operand and the old
+    //carry and returns the result and the new carry
+    fn shift_op8(
+        &mut self,
+        target: opcode::Op8,
+        op: impl FnOnce(u8, bool) -> (u8, bool),
+    ) -> Result<(), CpuError> {
+        let data = self.read_op8(target)?;
+        let (result, carry) = op(data, self.registers.flags.carry);
+        self.registers.flags.zero = result == 0;
+        self.registers.flags.neg = false;
+        self.registers.flags.half_carry = false;
+        self.registers.flags.carry = carry;
+        self.write_op8(target, result)
+    }
+
+    fn add_a(&mut self, data: u8, carry_in: bool) {
+        let a = self.registers.a;
+        let carry_in = carry_in as u8;
+        let sum = u16::from(a) + u16::from(data) + u16::from(carry_in);
+        let result = sum as u8;
+
+        self.registers.flags.zero = result == 0;
+        self.registers.flags.neg = false;
+        self.registers.flags.half_carry = (a & 0x0F) + (data & 0x0F) + carry_in > 0x0F;
+        self.registers.flags.carry = sum > 0xFF;
+        self.registers.a = result;
+    }
+
+    //returns the difference and leaves A alone, so CP can share it
+    fn sub_a(&mut self, data: u8, carry_in: bool) -> u8 {
+        let a = self.registers.a;
+        let carry_in = carry_in as u8;
+        let result = a.wrapping_sub(data).wrapping_sub(carry_in);
+
+        self.registers.flags.zero = result == 0;
+        self.registers.flags.neg = true;
+        self.registers.flags.half_carry = (a & 0x0F) < (data & 0x0F) + carry_in;
+        self.registers.flags.carry = u16::from(a) < u16::from(data) + u16::from(carry_in);
+        result
+    }
+
+    fn set_logic_flags(&mut self, half_carry: bool) {
+        self.registers.flags.zero = self.registers.a == 0;
+        self.registers.flags.neg = false;
+        self.registers.flags.half_carry = half_carry;
+        self.registers.flags.carry = false;
+    }
+
+    //SP plus a signed immediate, with H and C computed on the low byte as unsigned addition
+    fn add_sp_signed(&mut self, imm: u8) -> u16 {
+        let sp = self.registers.sp;
+        let [_, sp_lo] = sp.to_be_bytes();
+
+        self.registers.flags.zero = false;
+        self.registers.flags.neg = false;
+        self.registers.flags.half_carry = (sp_lo & 0x0F) + (imm & 0x0F) > 0x0F;
+        self.registers.flags.carry = u16::from(sp_lo) + u16::from(imm) > 0xFF;
+
+        sp.wrapping_add(imm as i8 as u16)
     }
 
     fn test_condition(&self, cond: opcode::Condition) -> bool {
@@ -984,9 +1056,346 @@ mod tests {
     #[test]
     fn add_consuming() {
         let mut x: u8 = 0xFF;
-        x.wrapping_add(1);
+        x = x.wrapping_add(1);
         println!("{}", x);
 
         assert_eq!(x, 0);
     }
+
+    fn cpu_with_program(program: &[u8]) -> Cpu<CpuRam> {
+        let registers = Registers {
+            pc: START_ADDR,
+            sp: 0xFFFE,
+            ..Default::default()
+        };
+        let mut ram = CpuRam::new();
+        ram.load(START_ADDR, program);
+        Cpu::with_bus(registers, ram)
+    }
+
+    #[test]
+    fn ld_u16_sp_writes_both_bytes_on_last_two_cycles() {
+        let mut cpu = cpu_with_program(&[0x08, 0x00, 0xC0]);
+        cpu.registers.sp = 0xBEEF;
+
+        assert_eq!(cpu.step().unwrap().cycles, 20);
+        assert_eq!(cpu.bus.activity[3], BusActivity::Write(0xC000, 0xEF));
+        assert_eq!(cpu.bus.activity[4], BusActivity::Write(0xC001, 0xBE));
+    }
+
+    #[test]
+    fn push_writes_after_internal_delay() {
+        let mut cpu = cpu_with_program(&[0xC5]);
+        cpu.registers.bc.set_both(0x1234);
+
+        assert_eq!(cpu.step().unwrap().cycles, 16);
+        assert_eq!(
+            cpu.bus.activity,
+            vec![
+                BusActivity::Read(START_ADDR, 0xC5),
+                BusActivity::Idle,
+                BusActivity::Write(0xFFFD, 0x12),
+                BusActivity::Write(0xFFFC, 0x34),
+            ]
+        );
+        assert_eq!(cpu.registers.sp, 0xFFFC);
+    }
+
+    #[test]
+    fn cycles_match_lookup() {
+        use opcode::{Condition, OpcodeUnprefixed};
+
+        for (opcode, info) in opcode::LOOKUP.iter().enumerate() {
+            let cond = match info.inst {
+                OpcodeUnprefixed::Illegal | OpcodeUnprefixed::Prefix => continue,
+                OpcodeUnprefixed::Jr(cond)
+                | OpcodeUnprefixed::Jp(cond, _)
+                | OpcodeUnprefixed::Call(cond)
+                | OpcodeUnprefixed::Ret(cond) => cond,
+                _ => Condition::Unconditional,
+            };
+
+            let mut cpu = cpu_with_program(&[opcode as u8]);
+            cpu.registers.flags.zero = cond == Condition::Zero;
+            cpu.registers.flags.carry = cond == Condition::Carry;
+            assert_eq!(cpu.step().unwrap().cycles, info.cycles, "{}", info.mnemonic);
+
+            if cond != Condition::Unconditional {
+                let mut cpu = cpu_with_program(&[opcode as u8]);
+                cpu.registers.flags.zero = cond == Condition::NotZero;
+                cpu.registers.flags.carry = cond == Condition::NotCarry;
+                let cycles = cpu.step().unwrap().cycles;
+                assert_eq!(cycles, info.cycles_no_branch, "{}", info.mnemonic);
+            }
+        }
+
+        for (opcode, info) in opcode::SECONDARY.iter().enumerate() {
+            let mut cpu = cpu_with_program(&[0xCB, opcode as u8]);
+            assert_eq!(cpu.step().unwrap().cycles, info.cycles, "{}", info.mnemonic);
+        }
+    }
+
+    #[test]
+    fn flags_match_lookup() {
+        use opcode::{FlagEffect, FlagEffects, OpcodeUnprefixed};
+
+        //F and an operand for every register, together they make most instructions flip each
+        //flag one way or the other
+        let cases = [
+            (0x00, 0x00),
+            (0xF0, 0xFF),
+            (0x50, 0x0F),
+            (0xA0, 0x80),
+            (0x30, 0x01),
+        ];
+        let check = |program: &[u8], effects: FlagEffects, mnemonic: &str| {
+            for &(f, value) in cases.iter() {
+                let mut cpu = cpu_with_program(program);
+                cpu.registers.set_af(u16::from_be_bytes([value, f]));
+                cpu.registers
+                    .bc
+                    .set_both(u16::from_be_bytes([value, value]));
+                cpu.registers
+                    .de
+                    .set_both(u16::from_be_bytes([value, value]));
+                cpu.registers.hl.set_both(0xC000 | u16::from(value));
+                cpu.bus.load(0xC000 | u16::from(value), &[value]);
+                let before = cpu.registers.flags.f_reg();
+                cpu.step().unwrap();
+                let after = cpu.registers.flags.f_reg();
+
+                for (bit, effect) in [7, 6, 5, 4].iter().zip(effects.all().iter()) {
+                    let (was, is) = (before.get_bit(*bit), after.get_bit(*bit));
+                    let ok = match effect {
+                        FlagEffect::Unchanged => is == was,
+                        FlagEffect::Reset => !is,
+                        FlagEffect::Set => is,
+                        FlagEffect::Changed => true,
+                    };
+                    assert!(
+                        ok,
+                        "{} {} F {:#04X} -> {:#04X}",
+                        mnemonic, effects, before, after
+                    );
+                }
+            }
+        };
+
+        for (opcode, info) in opcode::LOOKUP.iter().enumerate() {
+            if let OpcodeUnprefixed::Illegal | OpcodeUnprefixed::Prefix = info.inst {
+                continue;
+            }
+            check(&[opcode as u8, 0x0F, 0x80], info.flags, info.mnemonic);
+        }
+        for (opcode, info) in opcode::SECONDARY.iter().enumerate() {
+            check(&[0xCB, opcode as u8], info.flags, info.mnemonic);
+        }
+    }
+
+    #[test]
+    fn add_sets_half_carry_and_carry() {
+        let mut cpu = cpu_with_program(&asm!("ADD A,B"));
+        cpu.registers.a = 0x8F;
+        cpu.registers.bc.hi = 0x81;
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.registers.a, 0x10);
+        assert_eq!(cpu.registers.flags.f_reg(), 0b0011_0000);
+    }
+
+    #[test]
+    fn sbc_borrows_carry() {
+        let mut cpu = cpu_with_program(&asm!("SBC A,$0F"));
+        cpu.registers.a = 0x10;
+        cpu.registers.flags.carry = true;
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.registers.a, 0x00);
+        assert_eq!(cpu.registers.flags.f_reg(), 0b1110_0000);
+    }
+
+    #[test]
+    fn daa_after_bcd_add() {
+        //0x45 + 0x38 = 0x83 in BCD
+        let mut cpu = cpu_with_program(&asm!("ADD A,$38", "DAA"));
+        cpu.registers.a = 0x45;
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.registers.a, 0x83);
+        assert!(!cpu.registers.flags.carry);
+    }
+
+    #[test]
+    fn pop_af_masks_low_nibble() {
+        let mut cpu = cpu_with_program(&asm!("POP AF"));
+        cpu.bus.load(0xFFFE, &[0xFF, 0x12]);
+        cpu.registers.sp = 0xFFFE;
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.registers.af(), 0x12F0);
+    }
+
+    #[test]
+    fn add_sp_signed_flags_from_low_byte() {
+        let mut cpu = cpu_with_program(&asm!("ADD SP,-1"));
+        cpu.registers.sp = 0x0001;
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.registers.sp, 0x0000);
+        assert_eq!(cpu.registers.flags.f_reg(), 0b0011_0000);
+    }
+
+    #[test]
+    fn ld_b_b_breaks_in_test_mode() {
+        let mut cpu = cpu_with_program(&[0x40, 0x40]);
+        assert!(!cpu.step().unwrap().breakpoint);
+
+        cpu.set_test_mode(true);
+        let info = cpu.step().unwrap();
+        assert!(info.breakpoint);
+        assert_eq!(info.cycles, 4);
+    }
+
+    #[test]
+    fn trace_matches_gameboy_doctor_format() {
+        struct Shared(std::rc::Rc<std::cell::RefCell<Vec<u8>>>);
+        impl Write for Shared {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.borrow_mut().write(buf)
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut cpu = cpu_with_program(&asm!("NOP", "LD A,$42"));
+        cpu.registers.set_af(0x01B0);
+        let log = std::rc::Rc::default();
+        cpu.set_trace(Some(Box::new(Shared(std::rc::Rc::clone(&log)))));
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+
+        let log = String::from_utf8(log.borrow().clone()).unwrap();
+        assert_eq!(
+            log,
+            "A:01 F:B0 B:00 C:00 D:00 E:00 H:00 L:00 SP:FFFE PC:0100 PCMEM:00,3E,42,00\n\
+             A:01 F:B0 B:00 C:00 D:00 E:00 H:00 L:00 SP:FFFE PC:0101 PCMEM:3E,42,00,00\n"
+        );
+    }
+
+    #[test]
+    fn illegal_opcode_locks_up() {
+        let mut cpu = cpu_with_program(&[0xD3, 0x00]);
+
+        assert_eq!(cpu.step(), Err(CpuError::IllegalOpcode(0xD3)));
+        assert_eq!(cpu.step(), Err(CpuError::LockedUp(0xD3)));
+        assert_eq!(cpu.registers.pc, START_ADDR + 1);
+        assert_eq!(cpu.cycles_passed, 8);
+    }
+
+    #[test]
+    fn interrupt_dispatch() {
+        let mut cpu = cpu_with_program(&[0x00]);
+        cpu.ime = true;
+        cpu.bus.write_byte(0xFFFF, 0b0000_0101);
+        cpu.bus.write_byte(0xFF0F, 0b0000_0100);
+
+        let info = cpu.step().unwrap();
+        assert_eq!(info.cycles, 20);
+        assert_eq!(cpu.registers.pc, 0x50);
+        assert_eq!(cpu.pop(), START_ADDR);
+        assert_eq!(cpu.bus.read_byte(0xFF0F), 0);
+        assert!(!cpu.ime);
+    }
+
+    #[test]
+    fn ei_takes_effect_after_next_instruction() {
+        let mut cpu = cpu_with_program(&[0xFB, 0x00, 0x00]);
+        cpu.bus.write_byte(0xFFFF, 0b0000_0001);
+        cpu.bus.write_byte(0xFF0F, 0b0000_0001);
+
+        cpu.step().unwrap();
+        assert_eq!(cpu.step().unwrap().pc, START_ADDR + 1);
+        cpu.step().unwrap();
+        assert_eq!(cpu.registers.pc, 0x40);
+    }
+
+    #[test]
+    fn stop_skips_a_byte_and_waits_for_a_button() {
+        let mut cpu = cpu_with_program(&[0x10, 0x00, 0x3C]);
+        cpu.step().unwrap();
+        assert_eq!(cpu.registers.pc, START_ADDR + 2);
+        cpu.bus.activity.clear();
+        for _ in 0..10 {
+            assert_eq!(cpu.step().unwrap().cycles, 4);
+        }
+        assert_eq!(cpu.registers.pc, START_ADDR + 2);
+        //nothing else runs meanwhile
+        assert!(cpu.bus.activity.is_empty());
+
+        //an enabled timer interrupt does not wake it
+        cpu.bus.write_byte(0xFFFF, 1 << 2);
+        cpu.bus.write_byte(0xFF0F, 1 << 2);
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+        assert_eq!(cpu.registers.a, 0);
+
+        //a joypad request does, even disabled
+        cpu.bus.write_byte(0xFF0F, 1 << 4);
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+        assert_eq!(cpu.registers.a, 1);
+    }
+
+    #[test]
+    fn stop_resets_div_and_stops_the_clock() {
+        use crate::cartridge::{test_rom, with_program};
+
+        //NOP; JP 0x0150, then STOP
+        let rom = with_program(test_rom(0x00, 2), &[0x10, 0x00]);
+        let mut cpu = Cpu::with_model(Cartridge::new("test.gb", rom).unwrap(), Model::Dmg);
+        cpu.bus.write_byte(0xFF07, 0b101); //TAC: timer on, every 4 M-cycles
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+        assert_ne!(cpu.bus.peek_byte(0xFF04), 0);
+
+        cpu.step().unwrap();
+        let (tima, ly) = (cpu.bus.peek_byte(0xFF05), cpu.bus.peek_byte(0xFF44));
+        for _ in 0..1000 {
+            cpu.step().unwrap();
+        }
+        assert_eq!(cpu.bus.peek_byte(0xFF04), 0);
+        assert_eq!(cpu.bus.peek_byte(0xFF05), tima);
+        assert_eq!(cpu.bus.peek_byte(0xFF44), ly);
+    }
+
+    #[test]
+    fn halt_bug_repeats_next_byte() {
+        //HALT with IME off and an interrupt pending, then INC A read twice
+        let mut cpu = cpu_with_program(&[0x76, 0x3C]);
+        cpu.bus.write_byte(0xFFFF, 0b0000_0001);
+        cpu.bus.write_byte(0xFF0F, 0b0000_0001);
+
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+        assert_eq!(cpu.registers.a, 2);
+        assert_eq!(cpu.registers.pc, START_ADDR + 2);
+    }
+
+    #[test]
+    fn halt_bug_reads_opcode_as_operand() {
+        //LD A,u8 loads its own opcode, then the real operand 0x3C runs as INC A
+        let mut cpu = cpu_with_program(&[0x76, 0x3E, 0x3C]);
+        cpu.bus.write_byte(0xFFFF, 0b0000_0001);
+        cpu.bus.write_byte(0xFF0F, 0b0000_0001);
+
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+        assert_eq!(cpu.registers.a, 0x3E);
+        assert_eq!(cpu.registers.pc, START_ADDR + 2);
+        cpu.step().unwrap();
+        assert_eq!(cpu.registers.a, 0x3F);
+    }
 }
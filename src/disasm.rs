@@ -0,0 +1,212 @@
+//! Decodes instructions using the `LOOKUP`/`SECONDARY` tables. The operand placeholders in the
+//! mnemonics (`u8`, `u16`, `r8`, `a8`) say how many bytes follow the opcode and how to show them.
+
+use std::fmt;
+
+use crate::opcode::{
+    FlagEffects, Op16, OpcodeUnprefixed, PrefixedOpInfo, UnprefixedOpInfo, LOOKUP, SECONDARY,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    U8(u8),
+    U16(u16),
+    I8(i8),        //ADD SP,r8 and LD HL,SP+r8
+    Relative(u16), //JR target, already resolved against the next instruction
+    HighAddr(u16), //LDH, 0xFF00 + u8
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum OpInfo {
+    Unprefixed(&'static UnprefixedOpInfo),
+    Prefixed(&'static PrefixedOpInfo), //after 0xCB
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Instruction {
+    pub addr: u16,
+    pub len: u16,
+    pub info: OpInfo,
+    pub operand: Option<Operand>,
+}
+
+impl Instruction {
+    /// Decodes the instruction at `addr`, getting its bytes from `fetch`.
+    pub fn decode_with(addr: u16, mut fetch: impl FnMut(u16) -> u8) -> Instruction {
+        let opcode = fetch(addr);
+        let info = &LOOKUP[usize::from(opcode)];
+        if let OpcodeUnprefixed::Prefix = info.inst {
+            let opcode = fetch(addr.wrapping_add(1));
+            return Instruction {
+                addr,
+                len: 2,
+                info: OpInfo::Prefixed(&SECONDARY[usize::from(opcode)]),
+                operand: None,
+            };
+        }
+
+        //the table has the length, the placeholder says how to show the operand
+        let mnemonic = info.mnemonic;
+        let operand = if info.bytes == 1 {
+            None
+        } else if mnemonic.contains("u16") {
+            let lo = fetch(addr.wrapping_add(1));
+            let hi = fetch(addr.wrapping_add(2));
+            Some(Operand::U16(u16::from_le_bytes([lo, hi])))
+        } else if mnemonic.contains("a8") {
+            Some(Operand::HighAddr(
+                0xFF00 | u16::from(fetch(addr.wrapping_add(1))),
+            ))
+        } else if mnemonic.contains("r8") {
+            let offset = fetch(addr.wrapping_add(1)) as i8;
+            if let OpcodeUnprefixed::Jr(_) = info.inst {
+                let target = addr.wrapping_add(2).wrapping_add(offset as u16);
+                Some(Operand::Relative(target))
+            } else {
+                Some(Operand::I8(offset))
+            }
+        } else {
+            Some(Operand::U8(fetch(addr.wrapping_add(1))))
+        };
+
+        Instruction {
+            addr,
+            len: info.bytes,
+            info: OpInfo::Unprefixed(info),
+            operand,
+        }
+    }
+
+    /// Decodes the instruction at the start of `bytes`, which sit at `addr`. `None` if `bytes`
+    /// ends in the middle of it.
+    pub fn decode(bytes: &[u8], addr: u16) -> Option<Instruction> {
+        let mut truncated = false;
+        let inst = Instruction::decode_with(addr, |at| {
+            let index = usize::from(at.wrapping_sub(addr));
+            bytes.get(index).copied().unwrap_or_else(|| {
+                truncated = true;
+                0
+            })
+        });
+        if truncated {
+            None
+        } else {
+            Some(inst)
+        }
+    }
+
+    pub fn mnemonic(&self) -> &'static str {
+        match self.info {
+            OpInfo::Unprefixed(info) => info.mnemonic,
+            OpInfo::Prefixed(info) => info.mnemonic,
+        }
+    }
+
+    //address of the instruction that follows in memory
+    pub fn next_addr(&self) -> u16 {
+        self.addr.wrapping_add(self.len)
+    }
+
+    /// What the instruction does to the flags.
+    pub fn flags(&self) -> FlagEffects {
+        match self.info {
+            OpInfo::Unprefixed(info) => info.flags,
+            OpInfo::Prefixed(info) => info.flags,
+        }
+    }
+
+    //`None` for CB-prefixed instructions
+    pub fn unprefixed(&self) -> Option<OpcodeUnprefixed> {
+        match self.info {
+            OpInfo::Unprefixed(info) => Some(info.inst),
+            OpInfo::Prefixed(_) => None,
+        }
+    }
+
+    /// Where a JP, JR, CALL or RST goes, if that does not depend on registers.
+    pub fn target(&self) -> Option<u16> {
+        match (self.unprefixed()?, self.operand) {
+            (OpcodeUnprefixed::Jp(_, Op16::U16), Some(Operand::U16(target)))
+            | (OpcodeUnprefixed::Call(_), Some(Operand::U16(target)))
+            | (OpcodeUnprefixed::Jr(_), Some(Operand::Relative(target))) => Some(target),
+            (OpcodeUnprefixed::Rst(target), _) => Some(target),
+            _ => None,
+        }
+    }
+
+    /// Formats the instruction with its jump or call target shown as `label`.
+    pub fn with_label(&self, label: &str) -> String {
+        match self.operand {
+            Some(Operand::U16(_)) => self.mnemonic().replace("u16", label),
+            Some(Operand::Relative(_)) => self.mnemonic().replace("r8", label),
+            _ => self.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mnemonic = self.mnemonic();
+        let text = match self.operand {
+            None => mnemonic.to_string(),
+            Some(Operand::U8(value)) => mnemonic.replace("u8", &format!("${:02X}", value)),
+            Some(Operand::U16(value)) => mnemonic.replace("u16", &format!("${:04X}", value)),
+            Some(Operand::HighAddr(addr)) => mnemonic.replace("a8", &format!("${:04X}", addr)),
+            Some(Operand::Relative(target)) => mnemonic.replace("r8", &format!("${:04X}", target)),
+            Some(Operand::I8(offset)) => {
+                if mnemonic.contains("+r8") {
+                    mnemonic.replace("+r8", &format!("{:+}", offset))
+                } else {
+                    mnemonic.replace("r8", &offset.to_string())
+                }
+            }
+        };
+        f.write_str(&text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn disasm(bytes: &[u8], addr: u16) -> String {
+        Instruction::decode(bytes, addr).unwrap().to_string()
+    }
+
+    #[test]
+    fn resolves_operands() {
+        assert_eq!(disasm(&[0x01, 0x34, 0x12], 0), "LD BC,$1234");
+        assert_eq!(disasm(&[0x3E, 0x42], 0), "LD A,$42");
+        assert_eq!(disasm(&[0xE0, 0x44], 0), "LDH ($FF44),A");
+        assert_eq!(disasm(&[0x18, 0xFE], 0x0150), "JR $0150");
+        assert_eq!(disasm(&[0x20, 0x05], 0x0150), "JR NZ,$0157");
+        assert_eq!(disasm(&[0xE8, 0xFE], 0), "ADD SP,-2");
+        assert_eq!(disasm(&[0xF8, 0x05], 0), "LD HL,SP+5");
+        assert_eq!(disasm(&[0xCB, 0x46], 0), "BIT 0,(HL)");
+        assert_eq!(disasm(&[0x76], 0), "HALT");
+    }
+
+    #[test]
+    fn targets() {
+        let decode = |bytes: &[u8]| Instruction::decode(bytes, 0x0150).unwrap();
+        assert_eq!(decode(&[0xC3, 0x00, 0x02]).target(), Some(0x0200));
+        assert_eq!(decode(&[0x38, 0x10]).target(), Some(0x0162));
+        assert_eq!(decode(&[0xFF]).target(), Some(0x0038));
+        assert_eq!(decode(&[0xE9]).target(), None); //JP HL
+        assert_eq!(decode(&[0x01, 0x00, 0x02]).target(), None);
+        assert_eq!(
+            decode(&[0xCC, 0x00, 0x40]).with_label("Func"),
+            "CALL Z,Func"
+        );
+    }
+
+    #[test]
+    fn lengths() {
+        let len = |bytes: &[u8]| Instruction::decode(bytes, 0).unwrap().len;
+        assert_eq!(len(&[0x00]), 1);
+        assert_eq!(len(&[0x10, 0x00]), 2);
+        assert_eq!(len(&[0xCB, 0x37]), 2);
+        assert_eq!(len(&[0xCD, 0x00, 0x40]), 3);
+        assert!(Instruction::decode(&[0xCD, 0x00], 0).is_none());
+    }
+}
@@ -0,0 +1,52 @@
+//! OAM DMA. Writing XX to FF46 copies XX00-XX9F into OAM, one byte per M-cycle after a cycle of
+//! setup. Sources from E000 up read work RAM, the way echo RAM does.
+
+pub const TRANSFER_LENGTH: u16 = 160;
+
+#[derive(Debug, Default)]
+pub struct Dma {
+    register: u8,          //FF46 reads back the last value written
+    starting: Option<u16>, //source of a transfer in its setup cycle
+    current: Option<u16>,  //address of the byte copied this M-cycle
+}
+
+impl Dma {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn read(&self) -> u8 {
+        self.register
+    }
+
+    /// Starts a transfer from `data` * 0x100. One already running carries on until the new one
+    /// has finished its setup.
+    pub fn write(&mut self, data: u8) {
+        self.register = data;
+        self.starting = Some(u16::from(data) << 8);
+    }
+
+    /// Sets FF46 without starting a transfer, for the state the boot ROM leaves.
+    pub fn set_register(&mut self, data: u8) {
+        self.register = data;
+    }
+
+    /// The address the transfer reads during this M-cycle, `None` when no transfer is running.
+    pub fn source(&self) -> Option<u16> {
+        self.current
+    }
+
+    /// Advances the transfer by one M-cycle. Returns the address of the byte to copy to OAM
+    /// during that cycle, its low byte is the offset in OAM.
+    pub fn tick(&mut self) -> Option<u16> {
+        let copy = self.current;
+        self.current = match copy {
+            Some(addr) if addr & 0xFF < TRANSFER_LENGTH - 1 => Some(addr + 1),
+            _ => None,
+        };
+        if let Some(source) = self.starting.take() {
+            self.current = Some(source);
+        }
+        copy
+    }
+}
@@ -0,0 +1,267 @@
+//! Replays what the DMG boot ROM does for users without a dump: clears VRAM, draws the logo from
+//! the cartridge header, scrolls it down, plays the two-note ding, then compares the logo with
+//! `NINTENDO_LOGO` and checks the header checksum. The scroll loop polls LY the same way the boot
+//! ROM does, so it takes about as long.
+//!
+//! The DMG0 and MGB boot ROMs are replayed the same way, and their models only differ in the
+//! state left behind.
+
+use anyhow::{bail, Result};
+
+use crate::cartridge::NINTENDO_LOGO;
+use crate::mmu::Bus;
+use crate::model::draw_logo;
+
+//where the scroll starts, one pixel per step
+const LOGO_SCROLL: u8 = 0x64;
+//steps after the scroll before the boot ROM moves on
+const LOGO_PAUSE: u8 = 0x20;
+
+//the ding is two square wave notes on channel 1, triggered on these steps
+const NOTES: [(u8, u8); 2] = [(0x62, 0x83), (0x64, 0xC1)];
+
+//ld (hl-),a; bit 7,h; jr nz for each byte
+const CLEAR_VRAM_M_CYCLES: usize = 0x2000 * 7;
+//roughly what decoding the logo into tiles and writing the tile map takes
+const DRAW_LOGO_M_CYCLES: usize = 5_000;
+//roughly what comparing the logo and summing the header takes
+const CHECK_HEADER_M_CYCLES: usize = 1_500;
+
+struct HleBoot<'a, B: Bus> {
+    bus: &'a mut B,
+    m_cycles: usize,
+}
+
+impl<B: Bus> HleBoot<'_, B> {
+    fn tick(&mut self, m_cycles: usize) {
+        for _ in 0..m_cycles {
+            self.bus.tick();
+        }
+        self.m_cycles += m_cycles;
+    }
+
+    //ldh a,(LY), with the read on the last M-cycle
+    fn read_ly(&mut self) -> u8 {
+        self.tick(3);
+        self.bus.peek_byte(0xFF44)
+    }
+
+    //the boot ROM waits for 12 polls that see LY at 0x90, twice. 12 polls do not fit in one line,
+    //so each wait spills into the next frame and a step takes about two and a half frames.
+    fn wait_frames(&mut self) {
+        for _ in 0..2 {
+            let mut polls = 12;
+            while polls > 0 {
+                let ly = self.read_ly();
+                self.tick(2); //cp $90
+                if ly != 0x90 {
+                    self.tick(3); //jr nz taken
+                    continue;
+                }
+                self.tick(3); //jr nz not taken, dec c
+                polls -= 1;
+                self.tick(if polls > 0 { 3 } else { 2 });
+            }
+            self.tick(6); //dec e; jr nz; ld c,$0C
+        }
+    }
+
+    fn scroll_logo(&mut self) {
+        self.bus.write_byte(0xFF42, LOGO_SCROLL); //SCY
+        self.bus.write_byte(0xFF40, 0x91); //LCDC: LCD and background on
+
+        let mut scy = LOGO_SCROLL;
+        for step in 1..=LOGO_SCROLL + LOGO_PAUSE {
+            self.wait_frames();
+
+            if let Some(&(_, frequency)) = NOTES.iter().find(|&&(at, _)| at == step) {
+                self.bus.write_byte(0xFF13, frequency); //NR13
+                self.bus.write_byte(0xFF14, 0x87); //NR14: trigger
+            }
+
+            if step <= LOGO_SCROLL {
+                scy -= 1;
+                self.bus.write_byte(0xFF42, scy);
+            }
+            self.tick(20);
+        }
+    }
+
+    fn draw(&mut self) {
+        self.tick(CLEAR_VRAM_M_CYCLES);
+        for addr in 0x8000..=0x9FFF {
+            self.bus.write_byte(addr, 0);
+        }
+
+        //sound on, channel 1 at full volume on both sides
+        self.bus.write_byte(0xFF26, 0x80); //NR52
+        self.bus.write_byte(0xFF11, 0x80); //NR11
+        self.bus.write_byte(0xFF12, 0xF3); //NR12
+        self.bus.write_byte(0xFF25, 0xF3); //NR51
+        self.bus.write_byte(0xFF24, 0x77); //NR50
+        self.bus.write_byte(0xFF47, 0xFC); //BGP
+
+        let mut vram = vec![0; 0x2000];
+        draw_logo(&mut vram, &self.header_logo());
+        for (addr, &data) in (0x8000..=0x9FFF).zip(vram.iter()) {
+            self.bus.write_byte(addr, data);
+        }
+        self.tick(DRAW_LOGO_M_CYCLES);
+    }
+
+    fn header_logo(&self) -> [u8; 48] {
+        let mut logo = [0; 48];
+        for (addr, byte) in (0x0104..).zip(logo.iter_mut()) {
+            *byte = self.bus.peek_byte(addr);
+        }
+        logo
+    }
+
+    fn check_header(&mut self) -> Result<()> {
+        self.tick(CHECK_HEADER_M_CYCLES);
+
+        let logo = self.header_logo();
+        if let Some(offset) = logo
+            .iter()
+            .zip(NINTENDO_LOGO.iter())
+            .position(|(a, b)| a != b)
+        {
+            bail!(
+                "logo byte at {:#06X} does not match, the boot ROM would lock up",
+                0x0104 + offset
+            );
+        }
+
+        let checksum = (0x0134..=0x014C).fold(0u8, |sum: u8, addr| {
+            sum.wrapping_sub(self.bus.peek_byte(addr)).wrapping_sub(1)
+        });
+        let expected = self.bus.peek_byte(0x014D);
+        if checksum != expected {
+            bail!(
+                "header checksum is {:#04X}, expected {:#04X}, the boot ROM would lock up",
+                expected,
+                checksum
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Runs the boot sequence on a freshly powered-on `bus`. Returns the M-cycles it took. The caller
+/// still has to put the CPU and I/O registers in their post-boot state.
+pub fn run<B: Bus>(bus: &mut B) -> Result<usize> {
+    let mut boot = HleBoot { bus, m_cycles: 0 };
+    boot.draw();
+    boot.scroll_logo();
+    boot.check_header()?;
+
+    Ok(boot.m_cycles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::{test_rom, Cartridge};
+    use crate::cpu::Cpu;
+    use crate::mmu::Mmu;
+    use crate::model::Model;
+
+    fn cartridge(fix_checksum: bool) -> Cartridge {
+        let mut rom = test_rom(0x00, 2);
+        if fix_checksum {
+            rom[0x14D] = rom[0x134..=0x14C]
+                .iter()
+                .fold(0u8, |sum, &byte| sum.wrapping_sub(byte).wrapping_sub(1));
+        }
+        Cartridge::new("test.gb", rom).unwrap()
+    }
+
+    #[test]
+    fn scrolls_logo_and_dings() -> Result<()> {
+        let mut mmu = Mmu::new(cartridge(true));
+        let m_cycles = run(&mut mmu)?;
+
+        assert_eq!(mmu.peek_byte(0xFF42), 0); //SCY
+        assert_eq!(mmu.peek_byte(0xFF13), 0xC1); //second note
+        assert_eq!(mmu.peek_byte(0xFF14), 0x87);
+        assert_eq!(mmu.peek_byte(0x9910), 0x19); //(R)
+
+        //about two and a half frames per step
+        let frames = m_cycles / (114 * 154);
+        assert!((300..360).contains(&frames), "{} frames", frames);
+        Ok(())
+    }
+
+    #[test]
+    fn leaves_what_the_boot_rom_leaves() -> Result<()> {
+        //checked before finish_boot, which would overwrite all of it
+        let mut mmu = Mmu::new(cartridge(true));
+        run(&mut mmu)?;
+
+        let mut vram = vec![0; 0x2000];
+        Model::Dmg.boot_vram(&mut vram);
+        for (addr, &data) in (0x8000..=0x9FFF).zip(vram.iter()) {
+            assert_eq!(mmu.peek_byte(addr), data, "{:#06X}", addr);
+        }
+
+        //the registers the boot sequence writes
+        let written = [0xFF12, 0xFF24, 0xFF25, 0xFF40, 0xFF42, 0xFF47];
+        for (addr, data) in Model::Dmg.boot_io() {
+            if written.contains(&addr) {
+                assert_eq!(mmu.peek_byte(addr), data, "{:#06X}", addr);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn bad_checksum_fails() {
+        let mut mmu = Mmu::new(cartridge(false));
+        assert!(run(&mut mmu).is_err());
+    }
+
+    //flips a logo byte, Cartridge::new would not even load such a ROM
+    struct BadLogo(Mmu);
+
+    impl BadLogo {
+        fn corrupt(addr: u16, data: u8) -> u8 {
+            if addr == 0x0110 {
+                !data
+            } else {
+                data
+            }
+        }
+    }
+
+    impl Bus for BadLogo {
+        fn read_byte(&mut self, addr: u16) -> u8 {
+            Self::corrupt(addr, self.0.read_byte(addr))
+        }
+
+        fn write_byte(&mut self, addr: u16, data: u8) {
+            self.0.write_byte(addr, data);
+        }
+
+        fn peek_byte(&self, addr: u16) -> u8 {
+            Self::corrupt(addr, self.0.peek_byte(addr))
+        }
+
+        fn tick(&mut self) {
+            self.0.tick();
+        }
+    }
+
+    #[test]
+    fn bad_logo_fails() {
+        let mut bus = BadLogo(Mmu::new(cartridge(true)));
+        let err = run(&mut bus).unwrap_err();
+        assert!(err.to_string().contains("0x0110"), "{}", err);
+
+        //the logo on screen is the cartridge's, with the flipped byte
+        let mut vram = vec![0; 0x2000];
+        let mut logo = NINTENDO_LOGO;
+        logo[0x0C] = !logo[0x0C];
+        draw_logo(&mut vram, &logo);
+        assert_eq!(bus.0.ppu.vram[0x70..0x78], vram[0x70..0x78]);
+    }
+}
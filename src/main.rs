@@ -1,32 +1,255 @@
 #![allow(unused)]
 
 use anyhow::Context;
-use anyhow::Result;
+use anyhow::{bail, Result};
 use cartridge::Cartridge;
 use cpu::Cpu;
+use model::Model;
+use ppu::Renderer;
 use std::ffi::OsStr;
 use std::fs::read_to_string;
 use std::path::Path;
+#[cfg(test)]
+mod acid2_tests;
+#[macro_use]
+mod asm;
+mod blargg;
 mod cartridge;
 mod cpu;
+mod disasm;
+mod dma;
+mod hle_boot;
+mod mmu;
+mod model;
+mod mooneye;
 
 // mod opcode;
 mod opcode;
+mod ppu;
+mod rgbds;
+mod rom_disasm;
+mod serial;
+#[cfg(test)]
+mod sm83_tests;
+mod timer;
 mod util;
 
-fn main() -> Result<()> {
-    let path = Path::new("Tetris.gb");
-    let rom = std::fs::read(path)?;
+const USAGE: &str = "usage:
+    anotherboy <rom>                              print the cartridge header
+    anotherboy blargg <rom> [--max-cycles <n>]    run a Blargg test ROM headless
+    anotherboy mooneye <dir> [--max-cycles <n>]   run every mooneye test ROM under <dir>
+    anotherboy disasm <rom> [--out <file>] [--rgbds <dir>]
+                                                  disassemble by following jumps and calls,
+                                                  --rgbds writes source rgbasm can rebuild it from
+    anotherboy trace <rom> [--out <file>] [--doctor] [--boot-rom <file> | --hle-boot]
+                     [--model <model>] [--max-cycles <n>] [--permissive]
+                     [--renderer scanline|fifo] [--log-blocked]
+                                                  log the CPU state before every instruction,
+                                                  --doctor makes LY read 0x90 like gameboy-doctor,
+                                                  --boot-rom runs a DMG/CGB boot ROM first,
+                                                  --hle-boot plays the boot animation without one,
+                                                  --model is one of dmg0, dmg, mgb and defaults
+                                                  to dmg, SGB and CGB need their boot ROM, whose
+                                                  size picks DMG or CGB,
+                                                  --permissive lets VRAM and OAM through in any
+                                                  PPU mode, --renderer fifo draws dot by dot
+                                                  instead of whole lines, --log-blocked prints
+                                                  the accesses the PPU blocks to stderr, where
+                                                  turning the LCD off outside VBlank is always
+                                                  reported";
+
+//one minute of emulated time
+const TRACE_MAX_CYCLES: usize = 4_194_304 * 60;
+
+fn load_cartridge(path: &Path) -> Result<Cartridge> {
+    let rom = std::fs::read(path).with_context(|| format!("reading {}", path.display()))?;
     let file_name = path
         .file_name()
         .and_then(OsStr::to_str)
         .context("bad filename")?;
-    let cartridge = Cartridge::new(file_name, rom)?;
-    let cpu = Cpu::new(cartridge);
-    cpu.debug_header();
+    Cartridge::new(file_name, rom)
+}
+
+//exits with 0 when the ROM passed, 1 when it failed and 2 when it timed out
+fn run_blargg(args: &[String]) -> Result<()> {
+    let mut rom = None;
+    let mut max_cycles = blargg::DEFAULT_MAX_CYCLES;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--max-cycles" => {
+                let value = args.next().context("--max-cycles needs a value")?;
+                max_cycles = value.parse().context("bad value for --max-cycles")?;
+            }
+            path if rom.is_none() => rom = Some(Path::new(path)),
+            other => bail!("unexpected argument {}\n{}", other, USAGE),
+        }
+    }
+
+    let cartridge = load_cartridge(rom.context(USAGE)?)?;
+    let report = blargg::run(cartridge, max_cycles);
+    println!("{}", report.output);
+    println!("{:?} after {} cycles", report.verdict, report.cycles);
+    std::process::exit(report.verdict.exit_code());
+}
+
+//exits with 0 when every ROM passed, 1 otherwise
+fn run_mooneye(args: &[String]) -> Result<()> {
+    let mut dir = None;
+    let mut max_cycles = mooneye::DEFAULT_MAX_CYCLES;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--max-cycles" => {
+                let value = args.next().context("--max-cycles needs a value")?;
+                max_cycles = value.parse().context("bad value for --max-cycles")?;
+            }
+            path if dir.is_none() => dir = Some(Path::new(path)),
+            other => bail!("unexpected argument {}\n{}", other, USAGE),
+        }
+    }
+
+    let dir = dir.context(USAGE)?;
+    let results = mooneye::run_dir(dir, max_cycles)?;
+    print!("{}", mooneye::matrix(dir, &results));
+    let all_passed = results
+        .iter()
+        .all(|(_, report)| report.verdict == blargg::Verdict::Passed);
+    std::process::exit(if all_passed { 0 } else { 1 });
+}
+
+fn run_disasm(args: &[String]) -> Result<()> {
+    let mut rom = None;
+    let mut out = None;
+    let mut rgbds_dir = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" => out = Some(args.next().context("--out needs a file")?),
+            "--rgbds" => {
+                rgbds_dir = Some(Path::new(args.next().context("--rgbds needs a directory")?))
+            }
+            path if rom.is_none() => rom = Some(Path::new(path)),
+            other => bail!("unexpected argument {}\n{}", other, USAGE),
+        }
+    }
+
+    let path = rom.context(USAGE)?;
+    let rom = std::fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+    if let Some(dir) = rgbds_dir {
+        return rgbds::export_to(&rom, dir);
+    }
+
+    let disassembly = rom_disasm::RomDisassembly::new(&rom);
+    let listing = disassembly.listing();
+    match out {
+        Some(out) => std::fs::write(out, listing).with_context(|| format!("writing {}", out))?,
+        None => print!("{}", listing),
+    }
+    if !disassembly.unresolved.is_empty() {
+        eprintln!(
+            "{} jumps or calls into an unknown bank",
+            disassembly.unresolved.len()
+        );
+    }
+    Ok(())
+}
+
+fn run_trace(args: &[String]) -> Result<()> {
+    let mut rom = None;
+    let mut out = None;
+    let mut doctor_mode = false;
+    let mut boot_rom = None;
+    let mut hle_boot = false;
+    let mut model = None;
+    let mut max_cycles = TRACE_MAX_CYCLES;
+    let mut permissive = false;
+    let mut renderer = Renderer::Scanline;
+    let mut log_blocked = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" => out = Some(args.next().context("--out needs a file")?),
+            "--doctor" => doctor_mode = true,
+            "--boot-rom" => boot_rom = Some(args.next().context("--boot-rom needs a file")?),
+            "--hle-boot" => hle_boot = true,
+            "--model" => model = Some(args.next().context("--model needs a value")?.parse()?),
+            "--max-cycles" => {
+                let value = args.next().context("--max-cycles needs a value")?;
+                max_cycles = value.parse().context("bad value for --max-cycles")?;
+            }
+            "--permissive" => permissive = true,
+            "--renderer" => {
+                renderer = args.next().context("--renderer needs a value")?.parse()?;
+            }
+            "--log-blocked" => log_blocked = true,
+            path if rom.is_none() => rom = Some(Path::new(path)),
+            other => bail!("unexpected argument {}\n{}", other, USAGE),
+        }
+    }
+
+    let trace: Box<dyn std::io::Write> = match out {
+        Some(path) => Box::new(std::io::BufWriter::new(
+            std::fs::File::create(path).with_context(|| format!("creating {}", path))?,
+        )),
+        None => Box::new(std::io::BufWriter::new(std::io::stdout())),
+    };
+
+    if boot_rom.is_some() && model.is_some() {
+        bail!("--model can't be used with --boot-rom, the boot ROM decides the model");
+    }
+
+    let cartridge = load_cartridge(rom.context(USAGE)?)?;
+    let model = model.unwrap_or(Model::Dmg);
+    let mut cpu = match boot_rom {
+        Some(path) => {
+            let boot_rom = std::fs::read(path).with_context(|| format!("reading {}", path))?;
+            Cpu::with_boot_rom(cartridge, boot_rom)?
+        }
+        None if hle_boot => Cpu::with_hle_boot(cartridge, model)?,
+        None => Cpu::with_model(cartridge, model),
+    };
+    cpu.bus_mut().set_doctor_mode(doctor_mode);
+    cpu.bus_mut().set_permissive(permissive);
+    cpu.bus_mut().ppu.set_renderer(renderer);
+    cpu.bus_mut().ppu.log_hazards(true);
+    cpu.bus_mut().log_blocked_accesses(log_blocked);
+    cpu.set_trace(Some(trace));
+    while cpu.cycles() < max_cycles {
+        let pc = cpu.registers().pc;
+        cpu.step()?;
+        for access in cpu.bus_mut().take_blocked_accesses() {
+            eprintln!("{:04X}: {}", pc, access);
+        }
+        for hazard in cpu.bus_mut().ppu.take_hazards() {
+            eprintln!("{:04X}: {}", pc, hazard);
+        }
+    }
     Ok(())
 }
 
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("blargg") => run_blargg(&args[1..]),
+        Some("mooneye") => run_mooneye(&args[1..]),
+        Some("trace") => run_trace(&args[1..]),
+        Some("disasm") => run_disasm(&args[1..]),
+        Some(path) => {
+            let cartridge = load_cartridge(Path::new(path))?;
+            let cpu = Cpu::new(cartridge);
+            cpu.debug_header();
+            Ok(())
+        }
+        None => bail!(USAGE),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
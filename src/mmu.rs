@@ -0,0 +1,624 @@
+use std::fmt;
+
+use anyhow::{bail, Result};
+
+use crate::cartridge::Cartridge;
+use crate::dma::Dma;
+use crate::model::Model;
+use crate::ppu::{Mode, Ppu};
+use crate::serial::Serial;
+use crate::timer::Timer;
+use crate::util::Twiddling;
+
+const WRAM_BANK_SIZE: usize = 4 * 1024;
+const WRAM_BANKS: usize = 8; //bank 0 at C000, SVBK picks 1-7 for D000 in CGB mode
+const IO_SIZE: usize = 128;
+const HRAM_SIZE: usize = 127;
+
+const DMG_BOOT_ROM_SIZE: usize = 0x100;
+const CGB_BOOT_ROM_SIZE: usize = 0x900; //0x0100-0x01FF is left for the cartridge header
+
+pub const INT_VBLANK: u8 = 0;
+pub const INT_STAT: u8 = 1;
+pub const INT_TIMER: u8 = 2;
+pub const INT_SERIAL: u8 = 3;
+pub const INT_JOYPAD: u8 = 4;
+
+/// Everything the CPU can reach over its address bus.
+///
+/// Each `read_byte`/`write_byte` performed by the CPU takes one M-cycle, and the CPU calls
+/// `tick` once for every M-cycle (accesses and internal delays alike) so the rest of the system
+/// sees each access at the right point inside an instruction.
+pub trait Bus {
+    fn read_byte(&mut self, addr: u16) -> u8;
+    fn write_byte(&mut self, addr: u16, data: u8);
+
+    /// Reads without taking time or counting as an access, for the CPU's interrupt checks and
+    /// for debugging.
+    fn peek_byte(&self, addr: u16) -> u8;
+
+    /// Advances everything besides the CPU by one M-cycle.
+    fn tick(&mut self);
+}
+
+/// A CPU access to VRAM or OAM while the PPU had it to itself. Reads give 0xFF and writes are
+/// dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockedAccess {
+    pub addr: u16,
+    pub write: Option<u8>, //`None` for a read
+    pub ly: u8,
+    pub dot: u16,
+    pub mode: Mode,
+}
+
+impl fmt::Display for BlockedAccess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.write {
+            Some(data) => write!(f, "write of ${:02X} to ${:04X}", data, self.addr)?,
+            None => write!(f, "read of ${:04X}", self.addr)?,
+        }
+        write!(
+            f,
+            " blocked at LY {} dot {} in mode {}",
+            self.ly, self.dot, self.mode as u8
+        )
+    }
+}
+
+pub struct Mmu {
+    pub cartridge: Cartridge,
+    boot_rom: Option<Vec<u8>>, //mapped over the cartridge until FF50 is written
+    pub ppu: Ppu,
+    wram: [u8; WRAM_BANK_SIZE * WRAM_BANKS],
+    wram_bank: u8,     //SVBK, where 0 selects bank 1 too
+    cgb_mode: bool,    //a CGB running its boot ROM or a CGB cartridge, with the extra banks
+    io: [u8; IO_SIZE], //registers without dedicated handling, stored as written
+    hram: [u8; HRAM_SIZE],
+    timer: Timer,
+    pub serial: Serial,
+    dma: Dma,
+    doctor_mode: bool, //LY always reads 0x90, as gameboy-doctor's reference logs assume
+    permissive: bool,  //VRAM and OAM stay accessible whatever the PPU is doing
+    blocked: Option<Vec<BlockedAccess>>,
+    interrupt_flag: u8,
+    interrupt_enable: u8,
+}
+
+impl Mmu {
+    pub fn new(cartridge: Cartridge) -> Self {
+        Self {
+            cartridge,
+            boot_rom: None,
+            ppu: Ppu::new(),
+            wram: [0; WRAM_BANK_SIZE * WRAM_BANKS],
+            wram_bank: 0,
+            cgb_mode: false,
+            io: [0; IO_SIZE],
+            hram: [0; HRAM_SIZE],
+            timer: Timer::new(),
+            serial: Serial::new(),
+            dma: Dma::new(),
+            doctor_mode: false,
+            permissive: false,
+            blocked: None,
+            interrupt_flag: 0,
+            interrupt_enable: 0,
+        }
+    }
+
+    /// The state `model`'s boot ROM leaves behind.
+    pub fn with_model(cartridge: Cartridge, model: Model) -> Self {
+        let mut mmu = Self::new(cartridge);
+        mmu.finish_boot(model);
+        mmu
+    }
+
+    /// Puts the timer, PPU, I/O registers and VRAM in the state `model`'s boot ROM leaves at
+    /// 0x0100, with the boot ROM unmapped.
+    pub fn finish_boot(&mut self, model: Model) {
+        self.boot_rom = None;
+        self.timer = Timer::with_counter(model.boot_div_counter());
+        let renderer = self.ppu.renderer();
+        self.ppu = Ppu::new();
+        self.ppu.set_renderer(renderer);
+        self.set_cgb_mode(false);
+        self.ppu.set_stat_write_bug(false); //the boot ROM's own writes are long past
+        self.dma = Dma::new();
+        for (addr, data) in model.boot_io() {
+            match addr {
+                0xFF46 => self.dma.set_register(data),
+                _ => self.write_io(addr, data),
+            }
+        }
+        model.boot_vram(&mut self.ppu.vram);
+        self.ppu.set_stat_write_bug(true);
+    }
+
+    /// Maps `boot_rom` over 0x0000-0x00FF, plus 0x0200-0x08FF for a CGB boot ROM.
+    pub fn with_boot_rom(cartridge: Cartridge, boot_rom: Vec<u8>) -> Result<Self> {
+        if boot_rom.len() != DMG_BOOT_ROM_SIZE && boot_rom.len() != CGB_BOOT_ROM_SIZE {
+            bail!(
+                "boot ROM is {} bytes, expected {} (DMG) or {} (CGB)",
+                boot_rom.len(),
+                DMG_BOOT_ROM_SIZE,
+                CGB_BOOT_ROM_SIZE
+            );
+        }
+
+        let mut mmu = Self::new(cartridge);
+        let cgb = boot_rom.len() == CGB_BOOT_ROM_SIZE;
+        mmu.ppu.set_stat_write_bug(!cgb);
+        //a CGB boot ROM runs in CGB mode whatever the cartridge, see the FF50 write
+        mmu.set_cgb_mode(cgb);
+        mmu.boot_rom = Some(boot_rom);
+        Ok(mmu)
+    }
+
+    pub fn boot_rom_mapped(&self) -> bool {
+        self.boot_rom.is_some()
+    }
+
+    pub fn request_interrupt(&mut self, interrupt: u8) {
+        self.interrupt_flag.set_bit(interrupt, true);
+    }
+
+    pub fn set_doctor_mode(&mut self, doctor_mode: bool) {
+        self.doctor_mode = doctor_mode;
+    }
+
+    /// Turns on the second VRAM bank, the WRAM banks and the CGB PPU features. Off for DMG
+    /// cartridges even on a CGB.
+    pub fn set_cgb_mode(&mut self, cgb_mode: bool) {
+        self.cgb_mode = cgb_mode;
+        self.ppu.set_cgb_mode(cgb_mode);
+        if !cgb_mode {
+            self.wram_bank = 0;
+        }
+    }
+
+    //C000-DFFF and its echo
+    fn wram_index(&self, addr: u16) -> usize {
+        let offset = usize::from(addr) % (2 * WRAM_BANK_SIZE);
+        if offset < WRAM_BANK_SIZE {
+            offset
+        } else {
+            usize::from(self.wram_bank.max(1)) * WRAM_BANK_SIZE + offset - WRAM_BANK_SIZE
+        }
+    }
+
+    /// Lets the CPU reach VRAM and OAM in any PPU mode, for debugging.
+    pub fn set_permissive(&mut self, permissive: bool) {
+        self.permissive = permissive;
+    }
+
+    /// Starts or stops recording the accesses the PPU blocks, see `take_blocked_accesses`.
+    /// Permissive mode still records the accesses that would have been blocked.
+    pub fn log_blocked_accesses(&mut self, log: bool) {
+        self.blocked = if log { Some(Vec::new()) } else { None };
+    }
+
+    /// The accesses blocked since the last call.
+    pub fn take_blocked_accesses(&mut self) -> Vec<BlockedAccess> {
+        self.blocked
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    //VRAM is the PPU's during drawing, OAM during OAM scan and drawing
+    fn ppu_blocks(&mut self, addr: u16, write: Option<u8>) -> bool {
+        let blocked = self.ppu.lcd_enabled()
+            && matches!(
+                (addr, self.ppu.mode()),
+                (0x8000..=0x9FFF, Mode::Drawing) | (0xFE00..=0xFE9F, Mode::OamScan | Mode::Drawing)
+            );
+        if !blocked {
+            return false;
+        }
+
+        if let Some(log) = &mut self.blocked {
+            log.push(BlockedAccess {
+                addr,
+                write,
+                ly: self.ppu.ly(),
+                dot: self.ppu.line_dot(),
+                mode: self.ppu.mode(),
+            });
+        }
+        !self.permissive
+    }
+
+    fn read_io(&self, addr: u16) -> u8 {
+        match addr {
+            0xFF01..=0xFF02 => self.serial.read(addr),
+            0xFF04..=0xFF07 => self.timer.read(addr),
+            0xFF44 if self.doctor_mode => 0x90,
+            0xFF40..=0xFF45 | 0xFF47..=0xFF4B => self.ppu.read(addr),
+            0xFF46 => self.dma.read(),
+            0xFF4F | 0xFF68..=0xFF6B => self.ppu.read(addr),
+            0xFF50 => 0xFF,
+            0xFF70 if self.cgb_mode => 0b1111_1000 | self.wram_bank,
+            0xFF70 => 0xFF,
+            0xFF0F => self.interrupt_flag | 0b1110_0000,
+            _ => self.io[usize::from(addr - 0xFF00)],
+        }
+    }
+
+    fn write_io(&mut self, addr: u16, data: u8) {
+        match addr {
+            0xFF01..=0xFF02 => self.serial.write(addr, data),
+            0xFF04..=0xFF07 => self.timer.write(addr, data),
+            0xFF40..=0xFF45 | 0xFF47..=0xFF4B => self.ppu.write(addr, data),
+            0xFF46 => self.dma.write(data),
+            0xFF4F | 0xFF68..=0xFF6B => self.ppu.write(addr, data),
+            0xFF50 => {
+                //can only be unmapped, never mapped back. A CGB boot ROM hands over in DMG mode
+                //unless the cartridge supports the CGB
+                if data != 0 && self.boot_rom.take().is_some() && self.cgb_mode {
+                    self.set_cgb_mode(self.cartridge.header.cgb_support());
+                }
+            }
+            0xFF70 if self.cgb_mode => self.wram_bank = data & 0b111,
+            0xFF70 => {}
+            0xFF0F => self.interrupt_flag = data & 0b0001_1111,
+            _ => self.io[usize::from(addr - 0xFF00)] = data,
+        }
+    }
+
+    //what OAM DMA reads at `addr`, everything from E000 up is work RAM
+    fn dma_read(&self, addr: u16) -> u8 {
+        match addr {
+            0xE000..=0xFFFF => self.peek_byte(addr - 0x2000),
+            _ => self.peek_byte(addr),
+        }
+    }
+
+    //while OAM DMA runs, the CPU cannot reach OAM, nor anything on the bus the transfer is
+    //reading from, where it sees the byte being transferred. HRAM and I/O are always there
+    fn dma_conflict(&self, addr: u16) -> Option<u8> {
+        let source = self.dma.source()?;
+        let vram_bus = |addr| (0x8000..=0x9FFF).contains(&addr);
+        match addr {
+            0xFE00..=0xFEFF => Some(0xFF),
+            0x0000..=0xFDFF if vram_bus(addr) == vram_bus(source) => Some(self.dma_read(source)),
+            _ => None,
+        }
+    }
+}
+
+impl Bus for Mmu {
+    fn read_byte(&mut self, addr: u16) -> u8 {
+        if let Some(data) = self.dma_conflict(addr) {
+            return data;
+        }
+        if self.ppu_blocks(addr, None) {
+            return 0xFF;
+        }
+        self.peek_byte(addr)
+    }
+
+    fn peek_byte(&self, addr: u16) -> u8 {
+        if let Some(boot_rom) = &self.boot_rom {
+            let index = usize::from(addr);
+            if index < DMG_BOOT_ROM_SIZE || (0x200..boot_rom.len()).contains(&index) {
+                return boot_rom[index];
+            }
+        }
+
+        match addr {
+            0x0000..=0x3FFF => self.cartridge.bank0()[usize::from(addr)], //16 KiB ROM bank 00
+            0x4000..=0x7FFF => {
+                let offset = addr - 0x4000;
+                self.cartridge.cur_bank()[usize::from(offset)]
+            } //16 KiB ROM Bank 01~NN
+            0x8000..=0x9FFF => self.ppu.cpu_vram()[usize::from(addr - 0x8000)], //8 KiB Video RAM (VRAM), banked on CGB
+            0xA000..=0xBFFF => self.cartridge.read_ram(addr - 0xA000),          //8 KiB External RAM
+            0xC000..=0xDFFF => self.wram[self.wram_index(addr)], //8 KiB Work RAM (WRAM), D000 banked on CGB
+            0xE000..=0xFDFF => self.wram[self.wram_index(addr)], //Mirror of C000~DDFF (ECHO RAM)
+            0xFE00..=0xFE9F => self.ppu.oam[usize::from(addr - 0xFE00)], //Sprite attribute table (OAM)
+            0xFEA0..=0xFEFF => 0x00,                                     //Not Usable
+            0xFF00..=0xFF7F => self.read_io(addr),                       //I/O Registers
+            0xFF80..=0xFFFE => self.hram[usize::from(addr - 0xFF80)],    //High RAM (HRAM)
+            0xFFFF => self.interrupt_enable, //Interrupt Enable register (IE)
+        }
+    }
+
+    fn write_byte(&mut self, addr: u16, data: u8) {
+        if self.dma_conflict(addr).is_some() || self.ppu_blocks(addr, Some(data)) {
+            return;
+        }
+        match addr {
+            0x0000..=0x7FFF => self.cartridge.write_rom(addr, data), //ROM, goes to the bank controller
+            0x8000..=0x9FFF => self.ppu.cpu_vram_mut()[usize::from(addr - 0x8000)] = data, //8 KiB Video RAM (VRAM), banked on CGB
+            0xA000..=0xBFFF => self.cartridge.write_ram(addr - 0xA000, data), //8 KiB External RAM
+            0xC000..=0xDFFF => {
+                let index = self.wram_index(addr);
+                self.wram[index] = data
+            } //8 KiB Work RAM (WRAM), D000 banked on CGB
+            0xE000..=0xFDFF => {
+                let index = self.wram_index(addr);
+                self.wram[index] = data
+            } //Mirror of C000~DDFF (ECHO RAM)
+            0xFE00..=0xFE9F => self.ppu.oam[usize::from(addr - 0xFE00)] = data, //Sprite attribute table (OAM)
+            0xFEA0..=0xFEFF => {}                                               //Not Usable
+            0xFF00..=0xFF7F => self.write_io(addr, data),                       //I/O Registers
+            0xFF80..=0xFFFE => self.hram[usize::from(addr - 0xFF80)] = data,    //High RAM (HRAM)
+            0xFFFF => self.interrupt_enable = data, //Interrupt Enable register (IE)
+        }
+    }
+
+    fn tick(&mut self) {
+        if let Some(source) = self.dma.tick() {
+            self.ppu.oam[usize::from(source & 0xFF)] = self.dma_read(source);
+        }
+        if self.timer.tick() {
+            self.request_interrupt(INT_TIMER);
+        }
+        if self.serial.tick() {
+            self.request_interrupt(INT_SERIAL);
+        }
+        self.interrupt_flag |= self.ppu.tick();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::test_rom;
+    use crate::dma::TRANSFER_LENGTH;
+
+    fn cartridge() -> Cartridge {
+        Cartridge::new("test.gb", test_rom(0x00, 2)).unwrap()
+    }
+
+    #[test]
+    fn boot_rom_unmaps_on_ff50_write() -> Result<()> {
+        let mut mmu = Mmu::with_boot_rom(cartridge(), vec![0xAA; DMG_BOOT_ROM_SIZE])?;
+        assert_eq!(mmu.read_byte(0x0000), 0xAA);
+        assert_eq!(mmu.read_byte(0x0104), 0xCE); //header stays visible
+
+        mmu.write_byte(0xFF50, 0x00);
+        assert!(mmu.boot_rom_mapped());
+        mmu.write_byte(0xFF50, 0x01);
+        assert!(!mmu.boot_rom_mapped());
+        assert_eq!(mmu.read_byte(0x0000), 0x00);
+        Ok(())
+    }
+
+    #[test]
+    fn cgb_boot_rom_skips_header() -> Result<()> {
+        let mmu = Mmu::with_boot_rom(cartridge(), vec![0xAA; CGB_BOOT_ROM_SIZE])?;
+        assert_eq!(mmu.peek_byte(0x00FF), 0xAA);
+        assert_eq!(mmu.peek_byte(0x0104), 0xCE);
+        assert_eq!(mmu.peek_byte(0x0200), 0xAA);
+        assert_eq!(mmu.peek_byte(0x08FF), 0xAA);
+        assert_eq!(mmu.peek_byte(0x0900), 0x00);
+
+        assert!(Mmu::with_boot_rom(cartridge(), vec![0; 0x200]).is_err());
+        Ok(())
+    }
+
+    //ticks until the transfer started by writing `source` to FF46 is done
+    fn run_dma(mmu: &mut Mmu, source: u8) {
+        mmu.write_byte(0xFF46, source);
+        for _ in 0..=TRANSFER_LENGTH {
+            mmu.tick();
+        }
+    }
+
+    #[test]
+    fn dma_takes_160_cycles_and_blocks_the_bus() {
+        let mut mmu = Mmu::new(cartridge());
+        for i in 0..0xA0 {
+            mmu.write_byte(0xC000 + i, i as u8);
+        }
+        mmu.write_byte(0xFF80, 0x42);
+        mmu.ppu.vram[0x10] = 0x24;
+
+        mmu.write_byte(0xFF46, 0xC0);
+        mmu.tick(); //setup
+        assert_eq!(mmu.read_byte(0xFF46), 0xC0);
+        for i in 0..TRANSFER_LENGTH {
+            assert_eq!(mmu.read_byte(0xFE00), 0xFF);
+            assert_eq!(mmu.read_byte(0xFF80), 0x42);
+            assert_eq!(mmu.read_byte(0x8010), 0x24); //the other bus
+                                                     //the source's bus gives whatever the transfer reads
+            assert_eq!(mmu.read_byte(0x0000), i as u8);
+            mmu.write_byte(0xDFFF, 0x99);
+            mmu.tick();
+        }
+        assert_eq!(mmu.read_byte(0xDFFF), 0x00);
+
+        let expected: Vec<u8> = (0..0xA0).collect();
+        assert_eq!(&mmu.ppu.oam[..], &expected[..]);
+        assert_eq!(mmu.read_byte(0xFE9F), 0x9F);
+        assert_eq!(mmu.read_byte(0x0104), 0xCE);
+    }
+
+    #[test]
+    fn dma_from_vram_blocks_vram() {
+        let mut mmu = Mmu::new(cartridge());
+        mmu.ppu.vram[0x00] = 0x55;
+        mmu.ppu.vram[0x05] = 0x77;
+        mmu.write_byte(0xC000, 0x11);
+        mmu.write_byte(0xFF46, 0x80);
+        mmu.tick();
+        assert_eq!(mmu.read_byte(0x9000), 0x55); //byte 0 of the source
+        assert_eq!(mmu.read_byte(0xC000), 0x11);
+        for _ in 0..TRANSFER_LENGTH {
+            mmu.tick();
+        }
+        assert_eq!(mmu.ppu.oam[5], 0x77);
+    }
+
+    #[test]
+    fn dma_sources() -> Result<()> {
+        let mut rom = test_rom(0x08, 2); //ROM+RAM
+        rom[0x149] = 0x02; //8 KiB
+        rom[0x4080] = 0xAB;
+        let mut mmu = Mmu::new(Cartridge::new("test.gb", rom)?);
+        mmu.write_byte(0xA010, 0xCD);
+        mmu.write_byte(0xC020, 0xEF);
+        mmu.write_byte(0xDE30, 0x12);
+
+        run_dma(&mut mmu, 0x40);
+        assert_eq!(mmu.ppu.oam[0x80], 0xAB);
+        run_dma(&mut mmu, 0xA0);
+        assert_eq!(mmu.ppu.oam[0x10], 0xCD);
+        //echo RAM, and on past it
+        run_dma(&mut mmu, 0xE0);
+        assert_eq!(mmu.ppu.oam[0x20], 0xEF);
+        run_dma(&mut mmu, 0xFE);
+        assert_eq!(mmu.ppu.oam[0x30], 0x12);
+        Ok(())
+    }
+
+    fn run_to_mode(mmu: &mut Mmu, mode: Mode) {
+        while mmu.ppu.mode() != mode {
+            mmu.tick();
+        }
+    }
+
+    #[test]
+    fn ppu_blocks_vram_and_oam() {
+        let mut mmu = Mmu::new(cartridge());
+        mmu.write_byte(0x8000, 0x11);
+        mmu.write_byte(0xFE00, 0x22);
+        mmu.write_byte(0xFF40, 0x80);
+        mmu.log_blocked_accesses(true);
+
+        assert_eq!(mmu.ppu.mode(), Mode::OamScan);
+        assert_eq!(mmu.read_byte(0x8000), 0x11);
+        assert_eq!(mmu.read_byte(0xFE00), 0xFF);
+        mmu.write_byte(0xFE00, 0x33);
+
+        run_to_mode(&mut mmu, Mode::Drawing);
+        assert_eq!(mmu.read_byte(0x8000), 0xFF);
+        mmu.write_byte(0x8000, 0x44);
+        assert_eq!(mmu.read_byte(0xFE00), 0xFF);
+
+        run_to_mode(&mut mmu, Mode::HBlank);
+        assert_eq!(mmu.read_byte(0x8000), 0x11);
+        assert_eq!(mmu.read_byte(0xFE00), 0x22);
+
+        let blocked = mmu.take_blocked_accesses();
+        assert_eq!(blocked.len(), 5);
+        assert_eq!(blocked[1].write, Some(0x33));
+        assert_eq!(
+            blocked[3].to_string(),
+            format!(
+                "write of $44 to $8000 blocked at LY 0 dot {} in mode 3",
+                blocked[3].dot
+            )
+        );
+        assert!(mmu.take_blocked_accesses().is_empty());
+
+        //off, everything is reachable
+        mmu.write_byte(0xFF40, 0x00);
+        assert_eq!(mmu.read_byte(0xFE00), 0x22);
+    }
+
+    #[test]
+    fn permissive_mode_still_logs() {
+        let mut mmu = Mmu::new(cartridge());
+        mmu.write_byte(0xFF40, 0x80);
+        mmu.set_permissive(true);
+        mmu.log_blocked_accesses(true);
+        run_to_mode(&mut mmu, Mode::Drawing);
+        mmu.write_byte(0x8000, 0x44);
+        assert_eq!(mmu.read_byte(0x8000), 0x44);
+        assert_eq!(mmu.take_blocked_accesses().len(), 2);
+    }
+
+    fn cgb_cartridge() -> Cartridge {
+        let mut rom = test_rom(0x00, 2);
+        rom[0x143] = 0x80;
+        rom[0x144..0x146].copy_from_slice(b"01");
+        rom[0x14B] = 0x33; //new licensee code, which the CGB flag needs
+        Cartridge::new("test.gbc", rom).unwrap()
+    }
+
+    //a CGB that ran its boot ROM
+    fn cgb(cartridge: Cartridge) -> Mmu {
+        let mut mmu = Mmu::with_boot_rom(cartridge, vec![0; CGB_BOOT_ROM_SIZE]).unwrap();
+        mmu.write_byte(0xFF50, 0x01);
+        mmu
+    }
+
+    #[test]
+    fn cgb_vram_and_wram_banks() {
+        let mut mmu = cgb(cgb_cartridge());
+        mmu.write_byte(0xFF40, 0x00);
+        assert_eq!(mmu.read_byte(0xFF4F), 0xFE);
+        mmu.write_byte(0x8000, 0x11);
+        mmu.write_byte(0xFF4F, 0x01);
+        assert_eq!(mmu.read_byte(0xFF4F), 0xFF);
+        mmu.write_byte(0x8000, 0x22);
+        assert_eq!((mmu.ppu.vram[0], mmu.ppu.vram1[0]), (0x11, 0x22));
+
+        assert_eq!(mmu.read_byte(0xFF70), 0xF8);
+        mmu.write_byte(0xC000, 0x33);
+        for bank in 1..8 {
+            mmu.write_byte(0xFF70, bank);
+            mmu.write_byte(0xD000, bank * 0x10);
+        }
+        mmu.write_byte(0xFF70, 0x00); //selects bank 1
+        assert_eq!(mmu.read_byte(0xD000), 0x10);
+        mmu.write_byte(0xFF70, 0xFD);
+        assert_eq!(mmu.read_byte(0xFF70), 0xFD);
+        assert_eq!(mmu.read_byte(0xD000), 0x50);
+        assert_eq!(mmu.read_byte(0xF000), 0x50); //echo
+        assert_eq!(mmu.read_byte(0xC000), 0x33);
+
+        mmu.write_byte(0xFF6A, 0x82);
+        mmu.write_byte(0xFF6B, 0x55);
+        assert_eq!(mmu.read_byte(0xFF6A), 0xC3);
+    }
+
+    #[test]
+    fn cgb_boot_rom_runs_in_cgb_mode() -> Result<()> {
+        for (cartridge, cgb_after) in [(cartridge(), false), (cgb_cartridge(), true)] {
+            let mut mmu = Mmu::with_boot_rom(cartridge, vec![0xAA; CGB_BOOT_ROM_SIZE])?;
+            assert_eq!(mmu.read_byte(0xFF4F), 0xFE); //VBK is there
+            mmu.write_byte(0xFF50, 0x01);
+            assert_eq!(mmu.cgb_mode, cgb_after);
+            assert_eq!(mmu.read_byte(0xFF4F), if cgb_after { 0xFE } else { 0xFF });
+        }
+
+        let mut mmu = Mmu::with_boot_rom(cartridge(), vec![0xAA; DMG_BOOT_ROM_SIZE])?;
+        assert!(!mmu.cgb_mode);
+        mmu.write_byte(0xFF50, 0x01);
+        assert!(!mmu.cgb_mode);
+        Ok(())
+    }
+
+    #[test]
+    fn cgb_banks_ignored_in_dmg_mode() {
+        //a CGB running a DMG cartridge too
+        for mut mmu in [
+            cgb(cartridge()),
+            Mmu::with_model(cgb_cartridge(), Model::Dmg),
+        ] {
+            mmu.write_byte(0xFF40, 0x00);
+            mmu.write_byte(0xFF4F, 0x01);
+            assert_eq!(mmu.read_byte(0xFF4F), 0xFF);
+            mmu.write_byte(0x8000, 0x22);
+            assert_eq!(mmu.ppu.vram[0], 0x22);
+
+            mmu.write_byte(0xD000, 0x44);
+            mmu.write_byte(0xFF70, 0x03);
+            assert_eq!(mmu.read_byte(0xFF70), 0xFF);
+            assert_eq!(mmu.read_byte(0xD000), 0x44);
+            assert_eq!(mmu.read_byte(0xFF68), 0xFF);
+        }
+    }
+
+    #[test]
+    fn boot_state_has_no_dma_running() {
+        let mut mmu = Mmu::with_model(cartridge(), Model::Dmg);
+        assert_eq!(mmu.read_byte(0xFF46), 0xFF);
+        mmu.tick();
+        assert_eq!(mmu.dma.source(), None);
+    }
+}
@@ -0,0 +1,210 @@
+//! The hardware revisions we can pretend to be, and the state each one's boot ROM leaves behind
+//! when it hands over to the cartridge at 0x0100.
+//!
+//! Only the monochrome DMG0, DMG and MGB are here. What the SGB and CGB boot ROMs leave depends on
+//! the cartridge (and the SNES for the SGB), DIV in particular, so those start from their real
+//! boot ROM instead, see `Mmu::with_boot_rom`.
+
+use std::str::FromStr;
+
+use anyhow::{bail, Error, Result};
+
+use crate::cartridge::NINTENDO_LOGO;
+use crate::cpu::Registers;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Model {
+    Dmg0, //early Japanese DMG, different boot ROM
+    Dmg,
+    Mgb, //Game Boy Pocket/Light
+}
+
+impl Model {
+    /// CPU registers at 0x0100. On DMG and MGB, H and C are set unless the header checksum at
+    /// 0x014D is 0x00.
+    pub fn boot_registers(self, header_checksum: u8) -> Registers {
+        let checksum_flags = if header_checksum == 0 { 0x00 } else { 0x30 };
+        let (af, bc, de, hl) = match self {
+            Model::Dmg0 => (0x0100, 0xFF13, 0x00C1, 0x8403),
+            Model::Dmg => (0x0180 | checksum_flags, 0x0013, 0x00D8, 0x014D),
+            Model::Mgb => (0xFF80 | checksum_flags, 0x0013, 0x00D8, 0x014D),
+        };
+
+        let mut registers = Registers::default();
+        registers.set_af(af);
+        registers.bc.set_both(bc);
+        registers.de.set_both(de);
+        registers.hl.set_both(hl);
+        registers.sp = 0xFFFE;
+        registers.pc = 0x0100;
+        registers
+    }
+
+    /// The internal counter behind DIV at 0x0100.
+    pub fn boot_div_counter(self) -> u16 {
+        match self {
+            Model::Dmg0 => 0x1830,
+            Model::Dmg | Model::Mgb => 0xABCC,
+        }
+    }
+
+    /// I/O registers at 0x0100, besides DIV and LY.
+    pub fn boot_io(self) -> Vec<(u16, u8)> {
+        let mut io = vec![
+            (0xFF00, 0xCF), //P1
+            (0xFF01, 0x00), //SB
+            (0xFF02, 0x7E), //SC
+            (0xFF05, 0x00), //TIMA
+            (0xFF06, 0x00), //TMA
+            (0xFF07, 0xF8), //TAC
+            (0xFF0F, 0xE1), //IF
+            (0xFF10, 0x80), //NR10
+            (0xFF11, 0xBF), //NR11
+            (0xFF12, 0xF3), //NR12
+            (0xFF13, 0xFF), //NR13
+            (0xFF14, 0xBF), //NR14
+            (0xFF16, 0x3F), //NR21
+            (0xFF17, 0x00), //NR22
+            (0xFF18, 0xFF), //NR23
+            (0xFF19, 0xBF), //NR24
+            (0xFF1A, 0x7F), //NR30
+            (0xFF1B, 0xFF), //NR31
+            (0xFF1C, 0x9F), //NR32
+            (0xFF1D, 0xFF), //NR33
+            (0xFF1E, 0xBF), //NR34
+            (0xFF20, 0xFF), //NR41
+            (0xFF21, 0x00), //NR42
+            (0xFF22, 0x00), //NR43
+            (0xFF23, 0xBF), //NR44
+            (0xFF24, 0x77), //NR50
+            (0xFF25, 0xF3), //NR51
+            (0xFF26, 0xF1), //NR52
+            (0xFF40, 0x91), //LCDC
+            (0xFF41, 0x85), //STAT
+            (0xFF42, 0x00), //SCY
+            (0xFF43, 0x00), //SCX
+            (0xFF45, 0x00), //LYC
+            (0xFF46, 0xFF), //DMA
+            (0xFF47, 0xFC), //BGP
+            (0xFF48, 0xFF), //OBP0
+            (0xFF49, 0xFF), //OBP1
+            (0xFF4A, 0x00), //WY
+            (0xFF4B, 0x00), //WX
+        ];
+
+        //the CGB registers read 0xFF
+        for addr in [
+            0xFF4D, 0xFF4F, 0xFF51, 0xFF52, 0xFF53, 0xFF54, 0xFF55, 0xFF56, 0xFF70,
+        ] {
+            io.push((addr, 0xFF));
+        }
+        io
+    }
+
+    /// Fills `vram` (0x8000-0x9FFF) the way the boot ROM leaves it, with the logo and the (R)
+    /// mark.
+    pub fn boot_vram(self, vram: &mut [u8]) {
+        draw_logo(vram, &NINTENDO_LOGO);
+    }
+}
+
+//the (R) next to the logo, stored in the DMG boot ROM at 0x00D8
+const REGISTERED_MARK: [u8; 8] = [0x3C, 0x42, 0xB9, 0xA5, 0xB9, 0xA5, 0x42, 0x3C];
+
+//stretches each bit of a nibble to two pixels
+fn double_nibble(nibble: u8) -> u8 {
+    (0..4).fold(0, |acc, bit| {
+        if nibble & (1 << bit) != 0 {
+            acc | (0b11 << (bit * 2))
+        } else {
+            acc
+        }
+    })
+}
+
+/// Draws `logo` like the DMG boot ROM does with the one in the cartridge header: tiles
+/// 0x01-0x18 hold it scaled up twice, tile 0x19 the (R), and the tile map row at 0x9904/0x9924
+/// shows them.
+pub fn draw_logo(vram: &mut [u8], logo: &[u8; 48]) {
+    //each nibble becomes two identical rows in the low bit plane
+    let mut addr = 0x0010;
+    for &byte in logo.iter() {
+        for nibble in [byte >> 4, byte & 0x0F].iter() {
+            let row = double_nibble(*nibble);
+            vram[addr] = row;
+            vram[addr + 2] = row;
+            addr += 4;
+        }
+    }
+    for &row in REGISTERED_MARK.iter() {
+        vram[addr] = row;
+        addr += 2;
+    }
+
+    vram[0x1910] = 0x19;
+    for tile in 1..=0x0C {
+        vram[0x1903 + tile] = tile as u8;
+        vram[0x1923 + tile] = tile as u8 + 0x0C;
+    }
+}
+
+impl FromStr for Model {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let model = match s.to_ascii_lowercase().as_str() {
+            "dmg0" => Model::Dmg0,
+            "dmg" => Model::Dmg,
+            "mgb" => Model::Mgb,
+            "sgb" | "sgb2" | "cgb" | "agb" => bail!(
+                "the state the {} boot ROM leaves is not modelled, run it with --boot-rom",
+                s
+            ),
+            other => bail!("unknown model {}, expected one of dmg0, dmg, mgb", other),
+        };
+        Ok(model)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksum_sets_half_carry_and_carry() {
+        assert_eq!(Model::Dmg.boot_registers(0x00).af(), 0x0180);
+        assert_eq!(Model::Dmg.boot_registers(0x4D).af(), 0x01B0);
+        assert_eq!(Model::Mgb.boot_registers(0x4D).af(), 0xFFB0);
+
+        let registers = Model::Dmg0.boot_registers(0x4D);
+        assert_eq!(registers.af(), 0x0100);
+        assert_eq!(registers.hl.as_both(), 0x8403);
+    }
+
+    #[test]
+    fn logo_tiles_and_map() {
+        let mut vram = vec![0; 0x2000];
+        Model::Dmg.boot_vram(&mut vram);
+
+        //0xCE: 1100 -> 0xF0, 1110 -> 0xFC
+        assert_eq!(
+            &vram[0x10..0x18],
+            &[0xF0, 0x00, 0xF0, 0x00, 0xFC, 0x00, 0xFC, 0x00]
+        );
+        assert_eq!(&vram[0x190..0x194], &[0x3C, 0x00, 0x42, 0x00]);
+        assert_eq!(
+            &vram[0x1904..0x1911],
+            &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 0x19]
+        );
+        assert_eq!(vram[0x1924], 0x0D);
+        assert_eq!(vram[0x192F], 0x18);
+    }
+
+    #[test]
+    fn parses_names() {
+        assert_eq!("MGB".parse::<Model>().unwrap(), Model::Mgb);
+        assert!("gbc".parse::<Model>().is_err());
+        //no post-boot state for these, only their boot ROM
+        assert!("cgb".parse::<Model>().is_err());
+    }
+}
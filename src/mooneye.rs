@@ -0,0 +1,222 @@
+//! Headless runner for the mooneye-test-suite ROMs.
+//!
+//! The ROMs finish by executing `LD B,B`, which the CPU reports as a breakpoint in test mode. A
+//! passing test leaves the Fibonacci numbers 3/5/8/13/21/34 in B/C/D/E/H/L, a failing one fills
+//! them with 0x42.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::blargg::Verdict;
+use crate::cartridge::Cartridge;
+use crate::cpu::Cpu;
+use crate::util::find_roms;
+
+const PASS_PATTERN: [u8; 6] = [3, 5, 8, 13, 21, 34];
+const FAIL_PATTERN: [u8; 6] = [0x42; 6];
+
+//the tests finish within a few frames, anything still running after ten seconds is stuck
+pub const DEFAULT_MAX_CYCLES: usize = 4_194_304 * 10;
+
+#[derive(Debug)]
+pub struct Report {
+    pub verdict: Verdict,
+    pub registers: [u8; 6], //B, C, D, E, H, L when the test stopped
+    pub error: Option<String>,
+    pub cycles: usize,
+}
+
+fn result_registers(cpu: &Cpu) -> [u8; 6] {
+    let registers = cpu.registers();
+    [
+        registers.bc.hi,
+        registers.bc.lo,
+        registers.de.hi,
+        registers.de.lo,
+        registers.hl.hi,
+        registers.hl.lo,
+    ]
+}
+
+/// Runs the ROM until it hits `LD B,B` or `max_cycles` clock cycles have passed.
+pub fn run(cartridge: Cartridge, max_cycles: usize) -> Report {
+    let mut cpu = Cpu::new(cartridge);
+    cpu.set_test_mode(true);
+
+    let mut error = None;
+    let verdict = loop {
+        match cpu.step() {
+            Ok(info) if info.breakpoint => {
+                break if result_registers(&cpu) == PASS_PATTERN {
+                    Verdict::Passed
+                } else {
+                    //anything but the pass pattern counts, not just FAIL_PATTERN
+                    Verdict::Failed
+                };
+            }
+            Ok(_) => {}
+            Err(err) => {
+                error = Some(err.to_string());
+                break Verdict::Failed;
+            }
+        }
+
+        if cpu.cycles() >= max_cycles {
+            break Verdict::TimedOut;
+        }
+    };
+
+    Report {
+        verdict,
+        registers: result_registers(&cpu),
+        error,
+        cycles: cpu.cycles(),
+    }
+}
+
+/// Runs every ROM under `dir`. ROMs that fail to load are reported as failures.
+pub fn run_dir(dir: &Path, max_cycles: usize) -> Result<Vec<(PathBuf, Report)>> {
+    let mut results = Vec::new();
+    for path in find_roms(dir)? {
+        let rom = std::fs::read(&path)?;
+        let report = match Cartridge::new(&path.to_string_lossy(), rom) {
+            Ok(cartridge) => run(cartridge, max_cycles),
+            Err(err) => Report {
+                verdict: Verdict::Failed,
+                registers: [0; 6],
+                error: Some(format!("{:#}", err)),
+                cycles: 0,
+            },
+        };
+        results.push((path, report));
+    }
+    Ok(results)
+}
+
+/// Formats the results as one block per directory, one line per ROM, followed by a total.
+pub fn matrix(root: &Path, results: &[(PathBuf, Report)]) -> String {
+    let mut out = String::new();
+    let width = results
+        .iter()
+        .filter_map(|(path, _)| path.file_name())
+        .map(|name| name.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut group = None;
+    for (path, report) in results {
+        let dir = path.parent().unwrap_or(root);
+        let dir = dir.strip_prefix(root).unwrap_or(dir);
+        if group != Some(dir) {
+            let name = if dir.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                dir
+            };
+            writeln!(out, "{}", name.display()).unwrap();
+            group = Some(dir);
+        }
+
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let result = match report.verdict {
+            Verdict::Passed => "pass",
+            Verdict::Failed => "FAIL",
+            Verdict::TimedOut => "TIMEOUT",
+        };
+        write!(out, "  {:<width$}  {}", name, result, width = width).unwrap();
+        if let Some(error) = &report.error {
+            write!(out, " ({})", error).unwrap();
+        } else if report.verdict == Verdict::Failed && report.registers != FAIL_PATTERN {
+            write!(out, " (registers {:02X?})", report.registers).unwrap();
+        }
+        out.push('\n');
+    }
+
+    let passed = results
+        .iter()
+        .filter(|(_, report)| report.verdict == Verdict::Passed)
+        .count();
+    writeln!(out, "{}/{} passed", passed, results.len()).unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::{test_rom, with_program};
+
+    const DEFAULT_DIR: &str = "test-roms/mooneye";
+
+    fn cartridge_with_program(program: &[u8]) -> Cartridge {
+        Cartridge::new("test.gb", with_program(test_rom(0x00, 2), program)).unwrap()
+    }
+
+    //LD B,u8; LD C,u8; ...; LD L,u8; LD B,B
+    fn finish_with(values: [u8; 6]) -> Vec<u8> {
+        let mut program = Vec::new();
+        for (&opcode, &value) in [0x06, 0x0E, 0x16, 0x1E, 0x26, 0x2E]
+            .iter()
+            .zip(values.iter())
+        {
+            program.extend_from_slice(&[opcode, value]);
+        }
+        program.push(0x40);
+        program
+    }
+
+    #[test]
+    fn detects_pass_and_fail() {
+        let report = run(cartridge_with_program(&finish_with(PASS_PATTERN)), 100_000);
+        assert_eq!(report.verdict, Verdict::Passed);
+
+        let report = run(cartridge_with_program(&finish_with(FAIL_PATTERN)), 100_000);
+        assert_eq!(report.verdict, Verdict::Failed);
+        assert_eq!(report.registers, FAIL_PATTERN);
+    }
+
+    #[test]
+    fn times_out() {
+        let report = run(cartridge_with_program(&[0x18, 0xFE]), 1_000);
+        assert_eq!(report.verdict, Verdict::TimedOut);
+    }
+
+    #[test]
+    fn matrix_groups_by_directory() {
+        let report = |verdict| Report {
+            verdict,
+            registers: FAIL_PATTERN,
+            error: None,
+            cycles: 0,
+        };
+        let results = vec![
+            (PathBuf::from("roms/a/one.gb"), report(Verdict::Passed)),
+            (PathBuf::from("roms/a/two.gb"), report(Verdict::Failed)),
+            (PathBuf::from("roms/b/three.gb"), report(Verdict::TimedOut)),
+        ];
+        let expected =
+            "a\n  one.gb    pass\n  two.gb    FAIL\nb\n  three.gb  TIMEOUT\n1/3 passed\n";
+        assert_eq!(matrix(Path::new("roms"), &results), expected);
+    }
+
+    /// Runs every ROM under `MOONEYE_ROMS_DIR` (defaults to `test-roms/mooneye`), skipped when
+    /// the ROMs are not present.
+    #[test]
+    fn mooneye_roms() -> Result<()> {
+        let dir = std::env::var_os("MOONEYE_ROMS_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_DIR));
+        if !dir.is_dir() {
+            eprintln!("skipping mooneye test ROMs, {} not found", dir.display());
+            return Ok(());
+        }
+
+        let results = run_dir(&dir, DEFAULT_MAX_CYCLES)?;
+        eprint!("{}", matrix(&dir, &results));
+        assert!(results
+            .iter()
+            .all(|(_, report)| report.verdict == Verdict::Passed));
+        Ok(())
+    }
+}
@@ -0,0 +1,1743 @@
+//! The picture processing unit's timing: which mode it is in, LY, the LYC comparison and the
+//! interrupts they raise. A frame is 154 lines of 456 dots, four dots per M-cycle. Lines 0-143
+//! go through OAM scan, drawing and HBlank, lines 144-153 are VBlank. LY already reads 0 for
+//! most of line 153.
+//!
+//! Turning the LCD off blanks the screen and holds LY at 0 in mode 0 until it is turned back
+//! on. The first frame after that is not shown. Real hardware can be damaged by turning it off
+//! outside VBlank, so that can be recorded as a hazard.
+//!
+//! The STAT interrupt sources are OR-ed into a single line and the interrupt is requested when
+//! it goes high, so a source that becomes active while another one still is does not request
+//! another.
+//!
+//! Each line is rendered in one go when drawing ends, into a back buffer that becomes the
+//! visible frame when VBlank starts. The sprites on a line are picked during OAM scan, at most
+//! 10 in OAM order, and the one with the lowest X wins where they overlap. In CGB mode the BG
+//! map attributes in VRAM bank 1 pick the bank each tile comes from, flip it, choose one of the
+//! eight BG palettes and can put it over sprites. Sprites then pick their own bank and palette,
+//! the first one in OAM wins where they overlap, and LCDC bit 0 no longer hides the BG but
+//! puts every sprite on top when clear. Pixels come out as 15-bit colors from palette RAM, and
+//! the DMG's four shades as fixed greys in the same format.
+//!
+//! The slower `Renderer::Fifo` draws a pixel per dot through the BG and OBJ pixel FIFOs
+//! instead, so register writes in the middle of a line show from the next pixel, and drawing
+//! takes longer with fine scroll, the window and sprites, the way it does on hardware.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{bail, Error, Result};
+
+use crate::mmu::{INT_STAT, INT_VBLANK};
+use crate::util::Twiddling;
+
+pub const VRAM_SIZE: usize = 8 * 1024;
+pub const OAM_SIZE: usize = 160;
+
+pub const SCREEN_WIDTH: usize = 160;
+pub const SCREEN_HEIGHT: usize = 144;
+
+const DOTS_PER_M_CYCLE: usize = 4;
+const LINE_DOTS: u16 = 456;
+const OAM_SCAN_DOTS: u16 = 80;
+const DRAWING_DOTS: u16 = 172;
+const LINES: u8 = 154;
+const VBLANK_LINE: u8 = 144;
+const LAST_LINE_LY_DOTS: u16 = 4; //LY reads 153 this long, then 0
+
+//writable STAT bits, the interrupt sources
+const STAT_HBLANK: u8 = 3;
+const STAT_VBLANK: u8 = 4;
+const STAT_OAM_SCAN: u8 = 5;
+const STAT_LYC: u8 = 6;
+
+//LCDC bits
+const LCDC_BG_ENABLE: u8 = 0; //on DMG, also the window. In CGB mode, whether the BG can be on top
+const LCDC_OBJ_ENABLE: u8 = 1;
+const LCDC_OBJ_SIZE: u8 = 2; //8x16 sprites
+const LCDC_BG_MAP: u8 = 3;
+const LCDC_TILE_DATA: u8 = 4; //0x8000 with unsigned tile numbers instead of 0x9000 signed
+const LCDC_WINDOW_ENABLE: u8 = 5;
+const LCDC_WINDOW_MAP: u8 = 6;
+const LCDC_ENABLE: u8 = 7;
+
+//VRAM offsets of the two tile maps
+const MAP_9800: usize = 0x1800;
+const MAP_9C00: usize = 0x1C00;
+
+const SPRITES_PER_LINE: usize = 10;
+const SPRITE_FETCH_DOTS: u8 = 6; //after the BG fetcher finishes its tile
+
+//OAM attribute bits
+const ATTR_CGB_PALETTE: u8 = 0b111; //mask, CGB mode only
+const ATTR_BANK: u8 = 3; //CGB mode only
+const ATTR_PALETTE: u8 = 4; //OBP1 instead of OBP0
+const ATTR_X_FLIP: u8 = 5;
+const ATTR_Y_FLIP: u8 = 6;
+const ATTR_BEHIND_BG: u8 = 7; //only shows over BG color 0
+
+//BG map attribute bits, kept in VRAM bank 1 in CGB mode
+const BG_ATTR_BANK: u8 = 3;
+const BG_ATTR_X_FLIP: u8 = 5;
+const BG_ATTR_Y_FLIP: u8 = 6;
+const BG_ATTR_PRIORITY: u8 = 7; //colors 1-3 go over sprites
+
+//CGB palette RAM, reached through BCPS/BCPD and OCPS/OCPD
+const PALETTE_RAM_SIZE: usize = 64; //8 palettes of 4 colors, 2 bytes each
+const PALETTE_INDEX: u8 = 0b11_1111;
+const PALETTE_AUTO_INCREMENT: u8 = 7;
+
+const WHITE: u16 = 0x7FFF;
+//DMG shades 0-3, as 15-bit colors
+const DMG_SHADES: [u16; 4] = [WHITE, 0x56B5, 0x294A, 0x0000];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Renderer {
+    Scanline, //whole lines when drawing ends, which always takes 172 dots
+    Fifo,     //dot by dot through the pixel FIFOs
+}
+
+impl FromStr for Renderer {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let renderer = match s.to_ascii_lowercase().as_str() {
+            "scanline" => Renderer::Scanline,
+            "fifo" => Renderer::Fifo,
+            other => bail!("unknown renderer {}, expected scanline or fifo", other),
+        };
+        Ok(renderer)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FetchStep {
+    TileNumber,
+    DataLow,
+    DataHigh,
+    Push, //waits for the BG FIFO to empty
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct BgPixel {
+    color: u8,
+    attributes: u8, //from the BG map, 0 outside CGB mode
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ObjPixel {
+    color: u8,
+    attributes: u8,
+    sprite: usize, //OAM index, which decides overlaps in CGB mode
+}
+
+//a sprite found by the OAM scan, as the scan read it. Later OAM or LCDC writes don't move it to
+//another row of its tiles
+#[derive(Debug, Clone, Copy)]
+struct LineSprite {
+    index: usize, //in OAM
+    row: u8,      //within the sprite, before flipping
+    height: u8,
+    x: u8,
+    tile: u8,
+    attributes: u8,
+}
+
+//state of the FIFO renderer within one line of mode 3
+struct Fifo {
+    bg: VecDeque<BgPixel>,
+    obj: VecDeque<ObjPixel>,
+    step: FetchStep,
+    second_dot: bool, //fetch steps take two dots
+    fetch_x: u8,      //tile column, relative to SCX / 8 or the window's left edge
+    tile: u8,
+    attributes: u8, //of the tile being fetched
+    lo: u8,
+    hi: u8,
+    first_fetch: bool,             //the first tile of a line is fetched twice
+    window: bool,                  //fetching window tiles
+    discard: u8,                   //pixels to drop for SCX fine scroll or a window left of x = 0
+    x: u8,                         //next pixel on the LCD
+    sprites: VecDeque<LineSprite>, //not yet fetched, in the order they are drawn
+    sprite_fetch: Option<u8>,      //dots spent fetching the first of `sprites`
+}
+
+impl Fifo {
+    fn new(scx: u8, sprites: VecDeque<LineSprite>) -> Self {
+        Self {
+            bg: VecDeque::with_capacity(16),
+            obj: VecDeque::with_capacity(8),
+            step: FetchStep::TileNumber,
+            second_dot: false,
+            fetch_x: 0,
+            tile: 0,
+            attributes: 0,
+            lo: 0,
+            hi: 0,
+            first_fetch: true,
+            window: false,
+            discard: scx % 8,
+            x: 0,
+            sprites,
+            sprite_fetch: None,
+        }
+    }
+
+    fn restart_fetch(&mut self) {
+        self.step = FetchStep::TileNumber;
+        self.second_dot = false;
+    }
+}
+
+/// The LCD was turned off outside VBlank, which real hardware does not like.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LcdOffHazard {
+    pub ly: u8,
+    pub dot: u16,
+}
+
+impl fmt::Display for LcdOffHazard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "LCD turned off outside VBlank at LY {} dot {}",
+            self.ly, self.dot
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    HBlank = 0,
+    VBlank = 1,
+    OamScan = 2,
+    Drawing = 3,
+}
+
+pub struct Ppu {
+    pub vram: [u8; VRAM_SIZE],
+    pub vram1: [u8; VRAM_SIZE], //CGB only, more tiles and the BG map attributes
+    pub oam: [u8; OAM_SIZE],
+    cgb_mode: bool,
+    vram_bank: u8, //VBK, which bank the CPU sees
+    bg_palettes: [u8; PALETTE_RAM_SIZE],
+    obj_palettes: [u8; PALETTE_RAM_SIZE],
+    bcps: u8,
+    ocps: u8,
+    lcdc: u8,
+    stat: u8, //only the interrupt enables, mode and coincidence are computed on read
+    scy: u8,
+    scx: u8,
+    ly: u8,
+    lyc: u8,
+    bgp: u8,
+    obp0: u8,
+    obp1: u8,
+    wy: u8,
+    wx: u8,
+    mode: Mode,
+    stat_line: bool,      //the STAT sources OR-ed together
+    stat_write_bug: bool, //DMG: writing STAT enables every source for a moment
+    pending_interrupts: u8,
+    dot: u16,                      //within the current line
+    window_triggered: bool,        //LY has matched WY this frame
+    window_line: u8, //the window's own line counter, only counts lines it was drawn on
+    line_sprites: Vec<LineSprite>, //found by the scan of the current line
+    renderer: Renderer,
+    fifo: Option<Fifo>,                 //only while the FIFO renderer is drawing
+    back: Vec<u16>,                     //frame being drawn
+    frame: Vec<u16>,                    //last finished frame
+    skip_frame: bool,                   //the first frame after turning the LCD on is not shown
+    hazards: Option<Vec<LcdOffHazard>>, //only while logging
+}
+
+impl Ppu {
+    pub fn new() -> Self {
+        Self {
+            vram: [0; VRAM_SIZE],
+            vram1: [0; VRAM_SIZE],
+            oam: [0; OAM_SIZE],
+            cgb_mode: false,
+            vram_bank: 0,
+            bg_palettes: [0xFF; PALETTE_RAM_SIZE],
+            obj_palettes: [0xFF; PALETTE_RAM_SIZE],
+            bcps: 0,
+            ocps: 0,
+            lcdc: 0,
+            stat: 0,
+            scy: 0,
+            scx: 0,
+            ly: 0,
+            lyc: 0,
+            bgp: 0,
+            obp0: 0,
+            obp1: 0,
+            wy: 0,
+            wx: 0,
+            mode: Mode::HBlank,
+            stat_line: false,
+            stat_write_bug: true,
+            pending_interrupts: 0,
+            dot: 0,
+            window_triggered: false,
+            window_line: 0,
+            line_sprites: Vec::with_capacity(SPRITES_PER_LINE),
+            renderer: Renderer::Scanline,
+            fifo: None,
+            back: vec![WHITE; SCREEN_WIDTH * SCREEN_HEIGHT],
+            frame: vec![WHITE; SCREEN_WIDTH * SCREEN_HEIGHT],
+            skip_frame: false,
+            hazards: None,
+        }
+    }
+
+    /// The last complete frame, `SCREEN_WIDTH` by `SCREEN_HEIGHT` 15-bit colors row by row, red
+    /// in the low 5 bits and blue in the high ones. The DMG shades are greys from white to
+    /// black. White while the LCD is off.
+    pub fn frame(&self) -> &[u16] {
+        &self.frame
+    }
+
+    /// Starts or stops recording the times the LCD is turned off outside VBlank, see
+    /// `take_hazards`.
+    pub fn log_hazards(&mut self, log: bool) {
+        self.hazards = if log { Some(Vec::new()) } else { None };
+    }
+
+    /// The times the LCD was turned off outside VBlank since the last call.
+    pub fn take_hazards(&mut self) -> Vec<LcdOffHazard> {
+        self.hazards
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// LY as the CPU reads it.
+    pub fn ly(&self) -> u8 {
+        if self.ly == LINES - 1 && self.dot >= LAST_LINE_LY_DOTS {
+            0
+        } else {
+            self.ly
+        }
+    }
+
+    /// Whether a write to STAT briefly enables every source, which it does on everything before
+    /// the CGB.
+    pub fn set_stat_write_bug(&mut self, bug: bool) {
+        self.stat_write_bug = bug;
+    }
+
+    //dots into the current line
+    pub fn line_dot(&self) -> u16 {
+        self.dot
+    }
+
+    /// Enables VRAM bank 1, the BG map attributes and color palettes.
+    pub fn set_cgb_mode(&mut self, cgb_mode: bool) {
+        self.cgb_mode = cgb_mode;
+        if !cgb_mode {
+            self.vram_bank = 0;
+        }
+    }
+
+    /// The VRAM bank VBK selects.
+    pub fn cpu_vram(&self) -> &[u8; VRAM_SIZE] {
+        if self.vram_bank == 1 {
+            &self.vram1
+        } else {
+            &self.vram
+        }
+    }
+
+    pub fn cpu_vram_mut(&mut self) -> &mut [u8; VRAM_SIZE] {
+        if self.vram_bank == 1 {
+            &mut self.vram1
+        } else {
+            &mut self.vram
+        }
+    }
+
+    pub fn renderer(&self) -> Renderer {
+        self.renderer
+    }
+
+    /// Switches renderers, from the next line on.
+    pub fn set_renderer(&mut self, renderer: Renderer) {
+        self.renderer = renderer;
+    }
+
+    pub fn lcd_enabled(&self) -> bool {
+        self.lcdc.get_bit(LCDC_ENABLE)
+    }
+
+    /// Advances the PPU by one M-cycle. Returns the interrupts to request, as bits of IF.
+    pub fn tick(&mut self) -> u8 {
+        let mut interrupts = 0;
+        for _ in 0..DOTS_PER_M_CYCLE {
+            interrupts |= self.dot();
+        }
+        interrupts
+    }
+
+    fn dot(&mut self) -> u8 {
+        let mut interrupts = std::mem::take(&mut self.pending_interrupts);
+        if self.lcd_enabled() {
+            interrupts |= self.advance();
+        }
+
+        let stat_line = self.stat_sources(self.stat);
+        if stat_line && !self.stat_line {
+            interrupts |= 1 << INT_STAT;
+        }
+        self.stat_line = stat_line;
+        interrupts
+    }
+
+    //whether any of the sources enabled in `enables` is active
+    fn stat_sources(&self, enables: u8) -> bool {
+        if !self.lcd_enabled() {
+            return false;
+        }
+        let mode_source = match self.mode {
+            Mode::HBlank => Some(STAT_HBLANK),
+            Mode::VBlank => Some(STAT_VBLANK),
+            Mode::OamScan => Some(STAT_OAM_SCAN),
+            Mode::Drawing => None,
+        };
+        let mode_active = mode_source.is_some_and(|bit| enables.get_bit(bit));
+        mode_active || (enables.get_bit(STAT_LYC) && self.ly() == self.lyc)
+    }
+
+    //moves on by a dot with the LCD on, returns the VBlank interrupt when it starts
+    fn advance(&mut self) -> u8 {
+        self.dot += 1;
+        if self.dot == LINE_DOTS {
+            self.dot = 0;
+            self.ly = (self.ly + 1) % LINES;
+            return self.start_line();
+        }
+
+        if self.ly >= VBLANK_LINE {
+            return 0;
+        }
+        if self.dot == OAM_SCAN_DOTS {
+            self.scan_oam();
+            if self.renderer == Renderer::Fifo {
+                let sprites = self.sprites_by_x().into_iter().collect();
+                self.fifo = Some(Fifo::new(self.scx, sprites));
+            }
+            self.mode = Mode::Drawing;
+            return 0;
+        }
+        if self.mode != Mode::Drawing {
+            return 0;
+        }
+
+        let done = match self.fifo.take() {
+            Some(mut fifo) => {
+                let done = self.fifo_dot(&mut fifo);
+                if done {
+                    if fifo.window {
+                        self.window_line += 1;
+                    }
+                } else {
+                    self.fifo = Some(fifo);
+                }
+                done
+            }
+            None if self.dot == OAM_SCAN_DOTS + DRAWING_DOTS => {
+                self.render_line();
+                true
+            }
+            None => false,
+        };
+        if done {
+            self.mode = Mode::HBlank;
+        }
+        0
+    }
+
+    fn start_line(&mut self) -> u8 {
+        if self.ly == 0 {
+            self.window_triggered = false;
+            self.window_line = 0;
+        }
+        if self.ly == self.wy {
+            self.window_triggered = true;
+        }
+
+        match self.ly {
+            ly if ly < VBLANK_LINE => {
+                self.mode = Mode::OamScan;
+                0
+            }
+            VBLANK_LINE => {
+                if self.skip_frame {
+                    self.skip_frame = false;
+                } else {
+                    std::mem::swap(&mut self.frame, &mut self.back);
+                }
+                self.mode = Mode::VBlank;
+                1 << INT_VBLANK
+            }
+            _ => 0,
+        }
+    }
+
+    fn sprite_height(&self) -> u8 {
+        if self.lcdc.get_bit(LCDC_OBJ_SIZE) {
+            16
+        } else {
+            8
+        }
+    }
+
+    //the first 10 sprites in OAM that cover this line, whatever their X. Done in one go at the
+    //end of mode 2, which is when the result is first used
+    fn scan_oam(&mut self) {
+        let height = self.sprite_height();
+        let line = self.ly + 16; //OAM Y is the bottom of a 16 pixel sprite at the top
+        self.line_sprites.clear();
+        for index in 0..OAM_SIZE / 4 {
+            let [y, x, tile, attributes] = [0, 1, 2, 3].map(|field| self.oam[index * 4 + field]);
+            if line >= y && line < y + height {
+                self.line_sprites.push(LineSprite {
+                    index,
+                    row: line - y,
+                    height,
+                    x,
+                    tile,
+                    attributes,
+                });
+                if self.line_sprites.len() == SPRITES_PER_LINE {
+                    break;
+                }
+            }
+        }
+    }
+
+    fn window_map(&self) -> usize {
+        if self.lcdc.get_bit(LCDC_WINDOW_MAP) {
+            MAP_9C00
+        } else {
+            MAP_9800
+        }
+    }
+
+    fn bg_map(&self) -> usize {
+        if self.lcdc.get_bit(LCDC_BG_MAP) {
+            MAP_9C00
+        } else {
+            MAP_9800
+        }
+    }
+
+    //the attributes of the map entry at `addr`, which are all 0 outside CGB mode
+    fn map_attributes(&self, addr: usize) -> u8 {
+        if self.cgb_mode {
+            self.vram1[addr]
+        } else {
+            0
+        }
+    }
+
+    //the two bit planes of row `row` of a BG or window tile, flipped as `attributes` say
+    fn tile_row(&self, tile: u8, row: u8, attributes: u8) -> (u8, u8) {
+        let base = if self.lcdc.get_bit(LCDC_TILE_DATA) {
+            usize::from(tile) * 16
+        } else {
+            (0x1000 + i32::from(tile as i8) * 16) as usize
+        };
+        let row = if attributes.get_bit(BG_ATTR_Y_FLIP) {
+            7 - row
+        } else {
+            row
+        };
+        let vram = if attributes.get_bit(BG_ATTR_BANK) {
+            &self.vram1
+        } else {
+            &self.vram
+        };
+        let addr = base + usize::from(row) * 2;
+        let (lo, hi) = (vram[addr], vram[addr + 1]);
+        if attributes.get_bit(BG_ATTR_X_FLIP) {
+            (lo.reverse_bits(), hi.reverse_bits())
+        } else {
+            (lo, hi)
+        }
+    }
+
+    //pixel `x` in row `y` of the 256x256 map at `map`
+    fn map_pixel(&self, map: usize, x: u8, y: u8) -> BgPixel {
+        let addr = map + usize::from(y / 8) * 32 + usize::from(x / 8);
+        let attributes = self.map_attributes(addr);
+        let (lo, hi) = self.tile_row(self.vram[addr], y % 8, attributes);
+        let bit = 7 - x % 8;
+        BgPixel {
+            color: u8::from(hi.get_bit(bit)) << 1 | u8::from(lo.get_bit(bit)),
+            attributes,
+        }
+    }
+
+    //color number 0-3 of column `column` (0 is leftmost, before flipping) of a sprite on this line
+    fn sprite_column(&self, sprite: &LineSprite, column: u8) -> u8 {
+        let attributes = sprite.attributes;
+        let row = if attributes.get_bit(ATTR_Y_FLIP) {
+            sprite.height - 1 - sprite.row
+        } else {
+            sprite.row
+        };
+        let bit = if attributes.get_bit(ATTR_X_FLIP) {
+            column
+        } else {
+            7 - column
+        };
+        //8x16 sprites ignore bit 0 of the tile number, the rows carry on into the next tile
+        let tile = if sprite.height == 16 {
+            sprite.tile & 0xFE
+        } else {
+            sprite.tile
+        };
+        let vram = if self.cgb_mode && attributes.get_bit(ATTR_BANK) {
+            &self.vram1
+        } else {
+            &self.vram
+        };
+        let addr = usize::from(tile) * 16 + usize::from(row) * 2;
+        let (lo, hi) = (vram[addr], vram[addr + 1]);
+        u8::from(hi.get_bit(bit)) << 1 | u8::from(lo.get_bit(bit))
+    }
+
+    //color number 0-3 of pixel `x` of the line in sprite `sprite`, 0 if not covered
+    fn sprite_pixel(&self, sprite: &LineSprite, x: usize) -> u8 {
+        let column = x + 8;
+        let left = usize::from(sprite.x);
+        if column < left || column >= left + 8 {
+            return 0;
+        }
+        self.sprite_column(sprite, (column - left) as u8)
+    }
+
+    //sprites on this line from left to right, OAM order for ties
+    fn sprites_by_x(&self) -> Vec<LineSprite> {
+        if !self.lcdc.get_bit(LCDC_OBJ_ENABLE) {
+            return Vec::new();
+        }
+        let mut sprites = self.line_sprites.clone();
+        sprites.sort_by_key(|sprite| sprite.x); //stable, keeps OAM order
+        sprites
+    }
+
+    //sprites on this line from the one drawn on top, which in CGB mode is just OAM order
+    fn sorted_sprites(&self) -> Vec<LineSprite> {
+        if self.cgb_mode && self.lcdc.get_bit(LCDC_OBJ_ENABLE) {
+            self.line_sprites.clone()
+        } else {
+            self.sprites_by_x()
+        }
+    }
+
+    //the first opaque sprite pixel at `x`, which hides the sprites after it even when the BG
+    //then hides it. Transparent if there is none
+    fn sprite_over(&self, sprites: &[LineSprite], x: usize) -> ObjPixel {
+        sprites
+            .iter()
+            .map(|sprite| ObjPixel {
+                color: self.sprite_pixel(sprite, x),
+                attributes: sprite.attributes,
+                sprite: sprite.index,
+            })
+            .find(|pixel| pixel.color != 0)
+            .unwrap_or_default()
+    }
+
+    //whether LCDC bit 0 lets the BG and window show, which it always does in CGB mode
+    fn bg_shown(&self) -> bool {
+        self.cgb_mode || self.lcdc.get_bit(LCDC_BG_ENABLE)
+    }
+
+    //the color on screen where sprite pixel `obj` meets BG or window pixel `bg`
+    fn mix(&self, bg: BgPixel, obj: ObjPixel) -> u16 {
+        let bg_on_top = bg.color != 0
+            && (obj.attributes.get_bit(ATTR_BEHIND_BG) || bg.attributes.get_bit(BG_ATTR_PRIORITY))
+            && (!self.cgb_mode || self.lcdc.get_bit(LCDC_BG_ENABLE));
+        if obj.color != 0 && !bg_on_top {
+            self.obj_color(obj)
+        } else {
+            self.bg_color(bg)
+        }
+    }
+
+    fn bg_color(&self, bg: BgPixel) -> u16 {
+        if !self.bg_shown() {
+            //white on DMG, whatever BGP says
+            WHITE
+        } else if self.cgb_mode {
+            Self::palette_color(
+                &self.bg_palettes,
+                bg.attributes & ATTR_CGB_PALETTE,
+                bg.color,
+            )
+        } else {
+            DMG_SHADES[usize::from(self.bgp >> (bg.color * 2) & 0b11)]
+        }
+    }
+
+    fn obj_color(&self, obj: ObjPixel) -> u16 {
+        if self.cgb_mode {
+            Self::palette_color(
+                &self.obj_palettes,
+                obj.attributes & ATTR_CGB_PALETTE,
+                obj.color,
+            )
+        } else {
+            let palette = if obj.attributes.get_bit(ATTR_PALETTE) {
+                self.obp1
+            } else {
+                self.obp0
+            };
+            DMG_SHADES[usize::from(palette >> (obj.color * 2) & 0b11)]
+        }
+    }
+
+    //color `color` of palette `palette`, stored little-endian
+    fn palette_color(ram: &[u8; PALETTE_RAM_SIZE], palette: u8, color: u8) -> u16 {
+        let index = usize::from(palette) * 8 + usize::from(color) * 2;
+        u16::from_le_bytes([ram[index], ram[index + 1]]) & WHITE
+    }
+
+    fn render_line(&mut self) {
+        let bg_enabled = self.bg_shown();
+        let bg_map = self.bg_map();
+        let window_map = self.window_map();
+        //WX is the window's left edge plus 7, past 166 it is off screen
+        let window_x = if bg_enabled
+            && self.lcdc.get_bit(LCDC_WINDOW_ENABLE)
+            && self.window_triggered
+            && self.wx <= 166
+        {
+            Some(usize::from(self.wx).saturating_sub(7))
+        } else {
+            None
+        };
+
+        let sprites = self.sorted_sprites();
+
+        let line = usize::from(self.ly) * SCREEN_WIDTH;
+        for x in 0..SCREEN_WIDTH {
+            let bg = match window_x {
+                Some(start) if x >= start => {
+                    let window_x = (x + 7 - usize::from(self.wx)) as u8;
+                    self.map_pixel(window_map, window_x, self.window_line)
+                }
+                _ if bg_enabled => {
+                    let bg_x = (x as u8).wrapping_add(self.scx);
+                    self.map_pixel(bg_map, bg_x, self.ly.wrapping_add(self.scy))
+                }
+                _ => BgPixel::default(),
+            };
+            self.back[line + x] = self.mix(bg, self.sprite_over(&sprites, x));
+        }
+
+        if window_x.is_some() {
+            self.window_line += 1;
+        }
+    }
+
+    //one dot of mode 3 with the FIFO renderer, true once the last pixel of the line is out
+    fn fifo_dot(&mut self, fifo: &mut Fifo) -> bool {
+        if fifo.sprite_fetch.is_none() && !fifo.bg.is_empty() && !self.start_window(fifo) {
+            if self.sprite_due(fifo) {
+                fifo.sprite_fetch = Some(0);
+            } else {
+                self.shift_pixel(fifo);
+            }
+        }
+
+        //nothing is shifted out while a sprite is fetched, which waits for the BG fetcher to
+        //get to the end of its tile first
+        if let Some(dots) = fifo.sprite_fetch {
+            if fifo.step != FetchStep::Push {
+                self.fetch_dot(fifo);
+                if fifo.step != FetchStep::Push {
+                    return false;
+                }
+            }
+            if dots + 1 == SPRITE_FETCH_DOTS {
+                self.load_sprite(fifo);
+            } else {
+                fifo.sprite_fetch = Some(dots + 1);
+            }
+            return false;
+        }
+
+        if fifo.x as usize == SCREEN_WIDTH {
+            return true;
+        }
+        self.fetch_dot(fifo);
+        false
+    }
+
+    //at the pixel WX - 7 the BG FIFO is thrown away and fetching restarts from the window
+    fn start_window(&mut self, fifo: &mut Fifo) -> bool {
+        if fifo.window
+            || !self.bg_shown()
+            || !self.lcdc.get_bit(LCDC_WINDOW_ENABLE)
+            || !self.window_triggered
+            || fifo.x + 7 < self.wx
+        {
+            return false;
+        }
+        fifo.window = true;
+        fifo.bg.clear();
+        fifo.fetch_x = 0;
+        fifo.restart_fetch();
+        //a window left of the screen edge starts part way into its first tile
+        fifo.discard = 7u8.saturating_sub(self.wx);
+        true
+    }
+
+    //the next sprite starts at the next pixel, or further left when it sticks out past x = 0
+    fn sprite_due(&self, fifo: &Fifo) -> bool {
+        match fifo.sprites.front() {
+            Some(sprite) if self.lcdc.get_bit(LCDC_OBJ_ENABLE) => sprite.x <= fifo.x + 8,
+            _ => false,
+        }
+    }
+
+    //mixes the sprite's pixels into the OBJ FIFO, where those already there stay on top unless
+    //this sprite comes first in OAM in CGB mode
+    fn load_sprite(&mut self, fifo: &mut Fifo) {
+        let sprite = fifo.sprites.pop_front().unwrap();
+        fifo.sprite_fetch = None;
+        //columns left of the screen edge, or already shifted out. sprite_due checked this X
+        let skip = fifo.x + 8 - sprite.x;
+
+        while fifo.obj.len() < 8 {
+            fifo.obj.push_back(ObjPixel::default());
+        }
+        for column in skip..8 {
+            let color = self.sprite_column(&sprite, column);
+            let slot = &mut fifo.obj[usize::from(column - skip)];
+            if slot.color == 0 || (self.cgb_mode && color != 0 && sprite.index < slot.sprite) {
+                *slot = ObjPixel {
+                    color,
+                    attributes: sprite.attributes,
+                    sprite: sprite.index,
+                };
+            }
+        }
+    }
+
+    fn shift_pixel(&mut self, fifo: &mut Fifo) {
+        let bg = fifo.bg.pop_front().unwrap();
+        if fifo.discard > 0 {
+            fifo.discard -= 1;
+            return;
+        }
+        let obj = fifo.obj.pop_front().unwrap_or_default();
+
+        let bg = if self.bg_shown() {
+            bg
+        } else {
+            BgPixel::default()
+        };
+        let obj = if self.lcdc.get_bit(LCDC_OBJ_ENABLE) {
+            obj
+        } else {
+            ObjPixel::default()
+        };
+        self.back[usize::from(self.ly) * SCREEN_WIDTH + usize::from(fifo.x)] = self.mix(bg, obj);
+        fifo.x += 1;
+    }
+
+    //one dot of the BG fetcher, which reads the registers as it goes
+    fn fetch_dot(&mut self, fifo: &mut Fifo) {
+        let row = if fifo.window {
+            self.window_line
+        } else {
+            self.ly.wrapping_add(self.scy)
+        };
+
+        if fifo.step == FetchStep::Push {
+            if fifo.bg.is_empty() {
+                self.push_tile(fifo);
+            }
+            return;
+        }
+        if !fifo.second_dot {
+            fifo.second_dot = true;
+            return;
+        }
+        fifo.second_dot = false;
+
+        match fifo.step {
+            FetchStep::TileNumber => {
+                let (map, column) = if fifo.window {
+                    (self.window_map(), fifo.fetch_x)
+                } else {
+                    (self.bg_map(), self.scx / 8 + fifo.fetch_x)
+                };
+                let addr = map + usize::from(row / 8) * 32 + usize::from(column % 32);
+                fifo.tile = self.vram[addr];
+                fifo.attributes = self.map_attributes(addr);
+                fifo.step = FetchStep::DataLow;
+            }
+            FetchStep::DataLow => {
+                fifo.lo = self.tile_row(fifo.tile, row % 8, fifo.attributes).0;
+                fifo.step = FetchStep::DataHigh;
+            }
+            FetchStep::DataHigh => {
+                fifo.hi = self.tile_row(fifo.tile, row % 8, fifo.attributes).1;
+                fifo.step = FetchStep::Push;
+                if fifo.bg.is_empty() {
+                    self.push_tile(fifo);
+                }
+            }
+            FetchStep::Push => unreachable!(),
+        }
+    }
+
+    fn push_tile(&mut self, fifo: &mut Fifo) {
+        fifo.restart_fetch();
+        if fifo.first_fetch {
+            fifo.first_fetch = false;
+            return;
+        }
+        for bit in (0..8).rev() {
+            fifo.bg.push_back(BgPixel {
+                color: u8::from(fifo.hi.get_bit(bit)) << 1 | u8::from(fifo.lo.get_bit(bit)),
+                attributes: fifo.attributes,
+            });
+        }
+        fifo.fetch_x = fifo.fetch_x.wrapping_add(1);
+    }
+
+    pub fn read(&self, addr: u16) -> u8 {
+        match addr {
+            0xFF40 => self.lcdc,
+            0xFF41 => {
+                let coincidence = if self.ly() == self.lyc { 0b100 } else { 0 };
+                let mode = if self.lcd_enabled() {
+                    self.mode as u8
+                } else {
+                    0
+                };
+                0b1000_0000 | self.stat | coincidence | mode
+            }
+            0xFF42 => self.scy,
+            0xFF43 => self.scx,
+            0xFF44 => self.ly(),
+            0xFF45 => self.lyc,
+            0xFF47 => self.bgp,
+            0xFF48 => self.obp0,
+            0xFF49 => self.obp1,
+            0xFF4A => self.wy,
+            0xFF4B => self.wx,
+            0xFF4F if self.cgb_mode => 0b1111_1110 | self.vram_bank,
+            0xFF4F => 0xFF,
+            0xFF68 if self.cgb_mode => 0b0100_0000 | self.bcps,
+            0xFF69 if self.cgb_mode => self.bg_palettes[usize::from(self.bcps & PALETTE_INDEX)],
+            0xFF6A if self.cgb_mode => 0b0100_0000 | self.ocps,
+            0xFF6B if self.cgb_mode => self.obj_palettes[usize::from(self.ocps & PALETTE_INDEX)],
+            0xFF68..=0xFF6B => 0xFF,
+            _ => unreachable!("{:#06X} is not a PPU register", addr),
+        }
+    }
+
+    pub fn write(&mut self, addr: u16, data: u8) {
+        match addr {
+            0xFF40 => {
+                let was_enabled = self.lcd_enabled();
+                self.lcdc = data;
+                //the frame always restarts from the top, and nothing moves while off
+                if was_enabled != self.lcd_enabled() {
+                    if let Some(log) = &mut self.hazards {
+                        if was_enabled && self.mode != Mode::VBlank {
+                            log.push(LcdOffHazard {
+                                ly: self.ly,
+                                dot: self.dot,
+                            });
+                        }
+                    }
+                    self.ly = 0;
+                    self.dot = 0;
+                    self.fifo = None;
+                    if self.lcd_enabled() {
+                        self.mode = Mode::OamScan;
+                        self.skip_frame = true;
+                    } else {
+                        self.mode = Mode::HBlank;
+                        self.frame.iter_mut().for_each(|pixel| *pixel = WHITE);
+                    }
+                }
+            }
+            0xFF41 => {
+                //the moment every source is enabled only matters in HBlank, VBlank or on LYC
+                let glitch_sources = 1 << STAT_HBLANK | 1 << STAT_VBLANK | 1 << STAT_LYC;
+                if self.stat_write_bug && self.stat_sources(glitch_sources) && !self.stat_line {
+                    self.pending_interrupts |= 1 << INT_STAT;
+                    self.stat_line = true;
+                }
+                self.stat = data & 0b0111_1000;
+            }
+            0xFF42 => self.scy = data,
+            0xFF43 => self.scx = data,
+            0xFF44 => {} //read-only
+            0xFF45 => self.lyc = data,
+            0xFF47 => self.bgp = data,
+            0xFF48 => self.obp0 = data,
+            0xFF49 => self.obp1 = data,
+            0xFF4A => self.wy = data,
+            0xFF4B => self.wx = data,
+            0xFF4F if self.cgb_mode => self.vram_bank = data & 1,
+            0xFF4F => {}
+            0xFF68 if self.cgb_mode => self.bcps = data & 0b1011_1111,
+            0xFF69 if self.cgb_mode => {
+                Self::write_palette(&mut self.bg_palettes, &mut self.bcps, data)
+            }
+            0xFF6A if self.cgb_mode => self.ocps = data & 0b1011_1111,
+            0xFF6B if self.cgb_mode => {
+                Self::write_palette(&mut self.obj_palettes, &mut self.ocps, data)
+            }
+            0xFF68..=0xFF6B => {}
+            _ => unreachable!("{:#06X} is not a PPU register", addr),
+        }
+    }
+
+    //a write through BCPD or OCPD, which moves the index on if `spec` says so
+    fn write_palette(ram: &mut [u8; PALETTE_RAM_SIZE], spec: &mut u8, data: u8) {
+        ram[usize::from(*spec & PALETTE_INDEX)] = data;
+        if spec.get_bit(PALETTE_AUTO_INCREMENT) {
+            *spec = *spec & !PALETTE_INDEX | spec.wrapping_add(1) & PALETTE_INDEX;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled() -> Ppu {
+        let mut ppu = Ppu::new();
+        ppu.write(0xFF40, 0x80);
+        ppu
+    }
+
+    //ticks until `dots` dots have passed, returning the interrupts requested on the way
+    fn run(ppu: &mut Ppu, dots: usize) -> u8 {
+        assert_eq!(dots % DOTS_PER_M_CYCLE, 0);
+        (0..dots / DOTS_PER_M_CYCLE).fold(0, |interrupts, _| interrupts | ppu.tick())
+    }
+
+    #[test]
+    fn modes_within_a_line() {
+        let mut ppu = enabled();
+        assert_eq!(ppu.mode(), Mode::OamScan);
+        run(&mut ppu, 76);
+        assert_eq!(ppu.mode(), Mode::OamScan);
+        run(&mut ppu, 4);
+        assert_eq!(ppu.mode(), Mode::Drawing);
+        run(&mut ppu, 172);
+        assert_eq!(ppu.mode(), Mode::HBlank);
+        assert_eq!(ppu.read(0xFF41) & 0b11, 0);
+        run(&mut ppu, 204);
+        assert_eq!(ppu.mode(), Mode::OamScan);
+        assert_eq!(ppu.ly(), 1);
+    }
+
+    #[test]
+    fn vblank_once_per_frame() {
+        let mut ppu = enabled();
+        assert_eq!(run(&mut ppu, 144 * 456 - 4), 0);
+        assert_eq!(run(&mut ppu, 4), 1 << INT_VBLANK);
+        assert_eq!(ppu.ly(), 144);
+        assert_eq!(ppu.read(0xFF41) & 0b11, 1);
+
+        assert_eq!(run(&mut ppu, 10 * 456), 0);
+        assert_eq!(ppu.ly(), 0);
+        assert_eq!(ppu.mode(), Mode::OamScan);
+    }
+
+    #[test]
+    fn lyc_sets_coincidence_and_interrupt() {
+        let mut ppu = enabled();
+        ppu.write(0xFF45, 2);
+        ppu.write(0xFF41, 1 << STAT_LYC);
+        assert_eq!(ppu.read(0xFF41) & 0b100, 0);
+
+        assert_eq!(run(&mut ppu, 456), 0);
+        assert_eq!(run(&mut ppu, 456), 1 << INT_STAT);
+        assert_eq!(ppu.read(0xFF41), 0b1100_0110);
+    }
+
+    #[test]
+    fn mode_interrupts_follow_stat_enables() {
+        let mut ppu = enabled();
+        ppu.write(0xFF41, 1 << STAT_HBLANK);
+        assert_eq!(run(&mut ppu, 252), 1 << INT_STAT);
+        assert_eq!(run(&mut ppu, 204), 0);
+    }
+
+    //dots within the next `dots` that requested the STAT interrupt
+    fn stat_requests(ppu: &mut Ppu, dots: usize) -> usize {
+        (0..dots).filter(|_| ppu.dot().get_bit(INT_STAT)).count()
+    }
+
+    #[test]
+    fn overlapping_sources_request_once() {
+        let mut ppu = enabled();
+        ppu.set_stat_write_bug(false);
+        ppu.write(0xFF45, 1);
+        ppu.write(0xFF41, 1 << STAT_HBLANK | 1 << STAT_LYC);
+        //HBlank on line 0 runs straight into LY = LYC on line 1, which runs into its HBlank
+        assert_eq!(stat_requests(&mut ppu, 456), 1);
+        assert_eq!(stat_requests(&mut ppu, 456), 0);
+        assert_eq!(stat_requests(&mut ppu, 456), 1);
+
+        //VBlank and LYC together
+        ppu.write(0xFF45, 150);
+        ppu.write(0xFF41, 1 << STAT_VBLANK | 1 << STAT_LYC);
+        assert_eq!(stat_requests(&mut ppu, 141 * 456), 1);
+        assert_eq!(stat_requests(&mut ppu, 10 * 456), 0);
+    }
+
+    #[test]
+    fn line_153_reads_ly_0() {
+        let mut ppu = enabled();
+        ppu.write(0xFF41, 1 << STAT_LYC);
+        run(&mut ppu, 153 * 456);
+        assert_eq!(ppu.read(0xFF44), 153);
+        assert_eq!(stat_requests(&mut ppu, 3), 0);
+        assert_eq!(stat_requests(&mut ppu, 1), 1);
+        assert_eq!(ppu.read(0xFF44), 0);
+        assert_eq!(ppu.read(0xFF41) & 0b111, 0b101);
+
+        //line 0 proper doesn't request it again
+        assert_eq!(stat_requests(&mut ppu, 452 + 456), 0);
+        assert_eq!(ppu.read(0xFF44), 1);
+    }
+
+    #[test]
+    fn dmg_stat_write_enables_every_source() {
+        let mut ppu = enabled();
+        ppu.write(0xFF45, 5);
+        run(&mut ppu, 80);
+        ppu.write(0xFF41, 0); //drawing, nothing happens
+        assert_eq!(run(&mut ppu, 4), 0);
+
+        run(&mut ppu, 172);
+        assert_eq!(ppu.mode(), Mode::HBlank);
+        ppu.write(0xFF41, 0);
+        assert_eq!(run(&mut ppu, 4), 1 << INT_STAT);
+        //a source that really is enabled keeps the line high, so later writes request nothing
+        ppu.write(0xFF41, 1 << STAT_HBLANK);
+        assert_eq!(run(&mut ppu, 4), 1 << INT_STAT);
+        ppu.write(0xFF41, 1 << STAT_HBLANK);
+        assert_eq!(run(&mut ppu, 4), 0);
+
+        ppu.set_stat_write_bug(false);
+        ppu.write(0xFF41, 0);
+        ppu.write(0xFF41, 0);
+        assert_eq!(run(&mut ppu, 4), 0);
+    }
+
+    fn color(ppu: &Ppu, x: usize, y: usize) -> u16 {
+        ppu.frame()[y * SCREEN_WIDTH + x]
+    }
+
+    //the DMG shade 0-3 of a pixel
+    fn pixel(ppu: &Ppu, x: usize, y: usize) -> usize {
+        let color = color(ppu, x, y);
+        DMG_SHADES
+            .iter()
+            .position(|&shade| shade == color)
+            .unwrap_or_else(|| panic!("{:#06X} is not a DMG shade", color))
+    }
+
+    //until the end of the first frame that is shown after turning the LCD on
+    const FIRST_FRAME: usize = (154 + 144) * 456;
+
+    //tile 1 at 0x8010 with every pixel in color 1
+    fn with_tile(lcdc: u8) -> Ppu {
+        let mut ppu = Ppu::new();
+        for row in 0..8 {
+            ppu.vram[0x10 + row * 2] = 0xFF;
+        }
+        ppu.write(0xFF47, 0b1110_0100);
+        ppu.write(0xFF40, lcdc);
+        ppu
+    }
+
+    #[test]
+    fn renders_background_with_scroll_and_palette() {
+        let mut ppu = with_tile(0x91);
+        ppu.vram[MAP_9800] = 1;
+        run(&mut ppu, FIRST_FRAME);
+        assert_eq!(pixel(&ppu, 0, 0), 1);
+        assert_eq!(pixel(&ppu, 7, 7), 1);
+        assert_eq!(pixel(&ppu, 8, 0), 0);
+        assert_eq!(pixel(&ppu, 0, 8), 0);
+
+        ppu.write(0xFF42, 4); //SCY
+        ppu.write(0xFF43, 4); //SCX
+        ppu.write(0xFF47, 0b0000_1100); //color 1 is black, the rest white
+        run(&mut ppu, 154 * 456);
+        assert_eq!(pixel(&ppu, 3, 3), 3);
+        assert_eq!(pixel(&ppu, 4, 0), 0);
+        assert_eq!(pixel(&ppu, 0, 4), 0);
+        assert_eq!(pixel(&ppu, 0, 0), 3);
+
+        //wraps around the 256x256 map
+        ppu.write(0xFF43, 252);
+        run(&mut ppu, 154 * 456);
+        assert_eq!(pixel(&ppu, 4, 4), 0);
+        assert_eq!(pixel(&ppu, 4, 3), 3);
+    }
+
+    #[test]
+    fn bg_off_is_white() {
+        for renderer in [Renderer::Scanline, Renderer::Fifo] {
+            let mut ppu = with_tile(0x90);
+            ppu.set_renderer(renderer);
+            ppu.vram[MAP_9800] = 1;
+            ppu.write(0xFF47, 0xFF); //every color black
+            run(&mut ppu, FIRST_FRAME);
+            assert_eq!(pixel(&ppu, 0, 0), 0, "{:?}", renderer);
+            assert_eq!(pixel(&ppu, 8, 0), 0, "{:?}", renderer);
+        }
+    }
+
+    #[test]
+    fn signed_tile_numbers_start_at_9000() {
+        let mut ppu = with_tile(0x81);
+        ppu.vram[0x0800..0x0810]
+            .iter_mut()
+            .for_each(|byte| *byte = 0xFF); //tile 0x80 at 0x8800
+        ppu.vram[MAP_9800] = 0x80;
+        ppu.vram[MAP_9800 + 1] = 1; //0x9010, empty
+        run(&mut ppu, FIRST_FRAME);
+        assert_eq!(pixel(&ppu, 0, 0), 3);
+        assert_eq!(pixel(&ppu, 8, 0), 0);
+
+        //BG off shows color 0
+        ppu.write(0xFF40, 0x80);
+        run(&mut ppu, 154 * 456);
+        assert_eq!(pixel(&ppu, 0, 0), 0);
+    }
+
+    #[test]
+    fn window_keeps_its_own_line_counter() {
+        let mut ppu = Ppu::new();
+        //tile 1 has rows 0 and 2 in color 3
+        ppu.vram[0x10..0x12].copy_from_slice(&[0xFF, 0xFF]);
+        ppu.vram[0x14..0x16].copy_from_slice(&[0xFF, 0xFF]);
+        ppu.vram[MAP_9C00] = 1;
+        ppu.write(0xFF47, 0b1110_0100);
+        ppu.write(0xFF4A, 10); //WY
+        ppu.write(0xFF4B, 87); //WX, window from x = 80
+        ppu.write(0xFF40, 0xF1); //window at 0x9C00, BG at 0x9800
+        run(&mut ppu, 154 * 456); //not shown
+
+        run(&mut ppu, 12 * 456);
+        ppu.write(0xFF40, 0xD1); //window off for lines 12-19
+        run(&mut ppu, 8 * 456);
+        ppu.write(0xFF40, 0xF1);
+        run(&mut ppu, 124 * 456);
+
+        assert_eq!(pixel(&ppu, 79, 10), 0);
+        assert_eq!(pixel(&ppu, 80, 10), 3);
+        assert_eq!(pixel(&ppu, 87, 10), 3);
+        assert_eq!(pixel(&ppu, 88, 10), 0); //the next window tile is tile 0
+        assert_eq!(pixel(&ppu, 80, 11), 0);
+        assert_eq!(pixel(&ppu, 80, 12), 0);
+        //line 20 shows window line 2, not 10
+        assert_eq!(pixel(&ppu, 80, 20), 3);
+        assert_eq!(pixel(&ppu, 80, 9), 0);
+    }
+
+    //tile 2 in color 3 with a transparent top-left pixel, tile 3 empty
+    fn with_sprite_tiles(lcdc: u8) -> Ppu {
+        let mut ppu = with_tile(0);
+        ppu.vram[0x20..0x30]
+            .iter_mut()
+            .for_each(|byte| *byte = 0xFF);
+        ppu.vram[0x20..0x22].copy_from_slice(&[0x7F, 0x7F]);
+        ppu.write(0xFF48, 0b1110_0100); //OBP0
+        ppu.write(0xFF49, 0b0101_0100); //OBP1, color 3 is light grey
+        ppu.write(0xFF40, lcdc);
+        ppu
+    }
+
+    fn sprite(ppu: &mut Ppu, index: usize, y: u8, x: u8, tile: u8, attributes: u8) {
+        ppu.oam[index * 4..index * 4 + 4].copy_from_slice(&[y, x, tile, attributes]);
+    }
+
+    #[test]
+    fn ten_sprites_per_line() {
+        let mut ppu = with_sprite_tiles(0x93);
+        //off screen, but it still takes a slot
+        sprite(&mut ppu, 0, 16, 0, 2, 0);
+        for index in 1..11 {
+            sprite(&mut ppu, index, 16, index as u8 * 10, 2, 0);
+        }
+        run(&mut ppu, FIRST_FRAME);
+        assert_eq!(pixel(&ppu, 3, 0), 3);
+        assert_eq!(pixel(&ppu, 83, 7), 3);
+        assert_eq!(pixel(&ppu, 93, 0), 0); //the 11th sprite
+        assert_eq!(pixel(&ppu, 3, 8), 0);
+
+        ppu.write(0xFF40, 0x91); //sprites off
+        run(&mut ppu, 154 * 456);
+        assert_eq!(pixel(&ppu, 3, 0), 0);
+    }
+
+    #[test]
+    fn lower_x_wins_then_oam_order() {
+        let mut ppu = with_sprite_tiles(0x93);
+        sprite(&mut ppu, 0, 16, 12, 2, 1 << ATTR_PALETTE);
+        sprite(&mut ppu, 1, 16, 8, 2, 0);
+        sprite(&mut ppu, 2, 32, 40, 2, 1 << ATTR_PALETTE);
+        sprite(&mut ppu, 3, 32, 40, 2, 1 << ATTR_X_FLIP);
+        run(&mut ppu, FIRST_FRAME);
+        assert_eq!(pixel(&ppu, 5, 0), 3);
+        assert_eq!(pixel(&ppu, 9, 0), 1);
+        assert_eq!(pixel(&ppu, 33, 16), 1);
+        //color 0 is transparent, so the sprite behind shows through
+        assert_eq!(pixel(&ppu, 32, 16), 3);
+        assert_eq!(pixel(&ppu, 39, 16), 1);
+    }
+
+    #[test]
+    fn flips_and_tall_sprites() {
+        let mut ppu = with_sprite_tiles(0x93);
+        sprite(&mut ppu, 0, 16, 8, 2, 0);
+        sprite(&mut ppu, 1, 16, 24, 2, 1 << ATTR_X_FLIP);
+        sprite(&mut ppu, 2, 16, 40, 2, 1 << ATTR_Y_FLIP);
+        sprite(&mut ppu, 3, 16, 56, 3, 0); //tile 3 is empty in 8x8
+        run(&mut ppu, FIRST_FRAME);
+        assert_eq!(pixel(&ppu, 0, 0), 0);
+        assert_eq!(pixel(&ppu, 1, 0), 3);
+        assert_eq!(pixel(&ppu, 16, 0), 3);
+        assert_eq!(pixel(&ppu, 23, 0), 0);
+        assert_eq!(pixel(&ppu, 32, 0), 3);
+        assert_eq!(pixel(&ppu, 32, 7), 0);
+        assert_eq!(pixel(&ppu, 49, 0), 0);
+
+        ppu.write(0xFF40, 0x97); //8x16
+        run(&mut ppu, 154 * 456);
+        assert_eq!(pixel(&ppu, 49, 0), 3); //tile 3 becomes tile 2
+        assert_eq!(pixel(&ppu, 49, 8), 0);
+        assert_eq!(pixel(&ppu, 33, 7), 0);
+        assert_eq!(pixel(&ppu, 32, 15), 0);
+        assert_eq!(pixel(&ppu, 33, 15), 3);
+    }
+
+    //runs into mode 3 of line `line` of the first frame that is shown
+    fn into_drawing(ppu: &mut Ppu, line: u8) {
+        run(ppu, 154 * 456);
+        while ppu.ly != line || ppu.dot != OAM_SCAN_DOTS + 1 {
+            ppu.dot();
+        }
+    }
+
+    fn finish_frame(ppu: &mut Ppu) {
+        while ppu.ly != VBLANK_LINE {
+            ppu.dot();
+        }
+    }
+
+    #[test]
+    fn sprite_height_is_latched_by_the_scan() {
+        for renderer in [Renderer::Scanline, Renderer::Fifo] {
+            let mut ppu = with_sprite_tiles(0x97);
+            ppu.set_renderer(renderer);
+            //row 8 of a Y-flipped 8x16 sprite is row 7 of its top tile
+            sprite(&mut ppu, 0, 16, 8, 2, 1 << ATTR_Y_FLIP);
+            into_drawing(&mut ppu, 8);
+            ppu.write(0xFF40, 0x93); //8x8 from here on
+            finish_frame(&mut ppu);
+            assert_eq!(pixel(&ppu, 0, 8), 3, "{:?}", renderer);
+            assert_eq!(pixel(&ppu, 0, 9), 0, "{:?}", renderer);
+        }
+    }
+
+    #[test]
+    fn sprite_y_is_latched_by_the_scan() {
+        for renderer in [Renderer::Scanline, Renderer::Fifo] {
+            let mut ppu = with_sprite_tiles(0x93);
+            ppu.set_renderer(renderer);
+            sprite(&mut ppu, 0, 16, 8, 2, 0);
+            into_drawing(&mut ppu, 4);
+            ppu.oam[0] = 100; //what a DMA or a permissive write could do
+            finish_frame(&mut ppu);
+            assert_eq!(pixel(&ppu, 0, 4), 3, "{:?}", renderer);
+            assert_eq!(pixel(&ppu, 0, 5), 0, "{:?}", renderer);
+        }
+    }
+
+    #[test]
+    fn fifo_sprite_x_is_latched_by_the_scan() {
+        let mut ppu = with_sprite_tiles(0x93);
+        ppu.set_renderer(Renderer::Fifo);
+        sprite(&mut ppu, 0, 16, 8, 2, 0);
+        into_drawing(&mut ppu, 0);
+        while ppu.fifo.as_ref().unwrap().sprite_fetch.is_none() {
+            ppu.dot();
+        }
+        ppu.oam[1] = 200; //moved while its pixels are fetched
+        finish_frame(&mut ppu);
+        assert_eq!(pixel(&ppu, 1, 0), 3);
+    }
+
+    #[test]
+    fn sprites_behind_bg_only_show_over_color_0() {
+        let mut ppu = with_sprite_tiles(0x93);
+        ppu.vram[MAP_9800] = 1; //color 1
+        sprite(&mut ppu, 0, 16, 12, 2, 1 << ATTR_BEHIND_BG);
+        run(&mut ppu, FIRST_FRAME);
+        assert_eq!(pixel(&ppu, 5, 0), 1);
+        assert_eq!(pixel(&ppu, 9, 0), 3);
+
+        //with the BG off, sprites go on top of everything
+        ppu.write(0xFF40, 0x92);
+        run(&mut ppu, 154 * 456);
+        assert_eq!(pixel(&ppu, 5, 0), 3);
+    }
+
+    //scrolled BG, the window and a pile of sprites with every attribute
+    fn scene(renderer: Renderer) -> Ppu {
+        let mut ppu = with_sprite_tiles(0);
+        ppu.set_renderer(renderer);
+        for (i, tile) in ppu.vram[MAP_9800..].iter_mut().enumerate() {
+            *tile = (i % 3) as u8;
+        }
+        ppu.write(0xFF42, 3); //SCY
+        ppu.write(0xFF43, 5); //SCX
+        ppu.write(0xFF4A, 40); //WY
+        ppu.write(0xFF4B, 60); //WX
+        for index in 0..40 {
+            let (y, x) = (10 + index * 37 % 150, index * 29 % 170);
+            sprite(&mut ppu, index, y as u8, x as u8, 2, (index as u8) << 4);
+        }
+        ppu.write(0xFF40, 0xF3);
+        ppu
+    }
+
+    #[test]
+    fn fifo_draws_the_same_frames() {
+        let mut scanline = scene(Renderer::Scanline);
+        let mut fifo = scene(Renderer::Fifo);
+        for frame in 0..3 {
+            run(&mut scanline, 154 * 456);
+            run(&mut fifo, 154 * 456);
+            assert!(scanline.frame() == fifo.frame(), "frame {}", frame);
+        }
+        assert!(fifo.frame().iter().any(|&shade| shade != 0));
+    }
+
+    //dots spent drawing line `line`
+    fn drawing_dots(ppu: &mut Ppu, line: u8) -> usize {
+        while ppu.ly() != line || ppu.mode() != Mode::Drawing {
+            ppu.dot();
+        }
+        let mut dots = 0;
+        while ppu.mode() == Mode::Drawing {
+            ppu.dot();
+            dots += 1;
+        }
+        dots
+    }
+
+    #[test]
+    fn fifo_drawing_length_varies() {
+        let fifo = |lcdc| {
+            let mut ppu = with_sprite_tiles(0);
+            ppu.set_renderer(Renderer::Fifo);
+            ppu.write(0xFF40, lcdc);
+            ppu
+        };
+
+        let mut ppu = fifo(0x93);
+        assert_eq!(drawing_dots(&mut ppu, 0), 172);
+        ppu.write(0xFF43, 3);
+        assert_eq!(drawing_dots(&mut ppu, 1), 175);
+
+        //the window costs a fetch
+        let mut ppu = fifo(0xB3);
+        ppu.write(0xFF4A, 2);
+        ppu.write(0xFF4B, 87);
+        assert_eq!(drawing_dots(&mut ppu, 1), 172);
+        assert_eq!(drawing_dots(&mut ppu, 2), 178);
+
+        //6 dots per sprite, plus waiting for the BG fetcher to finish its tile
+        let mut ppu = fifo(0x93);
+        sprite(&mut ppu, 0, 16, 8, 2, 0);
+        assert_eq!(drawing_dots(&mut ppu, 0), 183);
+        sprite(&mut ppu, 0, 17, 13, 2, 0);
+        assert_eq!(drawing_dots(&mut ppu, 1), 178);
+        sprite(&mut ppu, 0, 18, 15, 2, 0);
+        sprite(&mut ppu, 1, 18, 15, 2, 0);
+        assert_eq!(drawing_dots(&mut ppu, 2), 184);
+        ppu.write(0xFF40, 0x91); //sprites off cost nothing
+        assert_eq!(drawing_dots(&mut ppu, 3), 172);
+
+        ppu.set_renderer(Renderer::Scanline);
+        ppu.write(0xFF40, 0x93);
+        sprite(&mut ppu, 0, 20, 8, 2, 0);
+        assert_eq!(drawing_dots(&mut ppu, 4), 172);
+    }
+
+    #[test]
+    fn fifo_applies_writes_mid_line() {
+        let mut ppu = with_tile(0x91);
+        ppu.set_renderer(Renderer::Fifo);
+        //tile 1 in even columns, tile 0 in odd ones
+        for column in (0..32).step_by(2) {
+            ppu.vram[MAP_9800 + column] = 1;
+        }
+        run(&mut ppu, 154 * 456); //not shown
+                                  //pixel x is shifted out on dot 93 + x of line 0
+        while ppu.dot != 132 {
+            ppu.dot();
+        }
+        ppu.write(0xFF47, 0b1100); //color 1 is black
+        ppu.write(0xFF43, 8); //the tile for x = 40-47 is already fetched
+        for _ in 0..144 * 456 - 132 {
+            ppu.dot();
+        }
+
+        assert_eq!(pixel(&ppu, 39, 0), 1);
+        assert_eq!(pixel(&ppu, 40, 0), 0);
+        assert_eq!(pixel(&ppu, 48, 0), 0); //column 7 instead of 6
+        assert_eq!(pixel(&ppu, 56, 0), 3);
+        assert_eq!(pixel(&ppu, 0, 1), 0);
+        assert_eq!(pixel(&ppu, 8, 1), 3);
+    }
+
+    #[test]
+    fn bg_attributes_pick_bank_and_flips() {
+        let cases = [
+            (true, Renderer::Scanline),
+            (true, Renderer::Fifo),
+            (false, Renderer::Scanline),
+        ];
+        for (cgb_mode, renderer) in cases {
+            let mut ppu = Ppu::new();
+            ppu.set_cgb_mode(cgb_mode);
+            ppu.set_renderer(renderer);
+            ppu.vram1[0x10] = 0x80; //tile 1 in bank 1, only its top-left pixel is set
+            ppu.vram[MAP_9800..MAP_9800 + 3].copy_from_slice(&[1, 1, 1]);
+            ppu.vram1[MAP_9800] = 1 << BG_ATTR_BANK;
+            ppu.vram1[MAP_9800 + 1] = 1 << BG_ATTR_BANK | 1 << BG_ATTR_X_FLIP;
+            ppu.vram1[MAP_9800 + 2] = 1 << BG_ATTR_BANK | 1 << BG_ATTR_Y_FLIP;
+            ppu.write(0xFF47, 0b1110_0100);
+            cgb_palette(&mut ppu, 0xFF68, 0, COLORS);
+            ppu.write(0xFF40, 0x91);
+            run(&mut ppu, FIRST_FRAME);
+
+            let (set, clear) = if cgb_mode {
+                (COLORS[1], COLORS[0])
+            } else {
+                (WHITE, WHITE) //bank 1 does not exist
+            };
+            assert_eq!(color(&ppu, 0, 0), set);
+            assert_eq!(color(&ppu, 8, 0), clear);
+            assert_eq!(color(&ppu, 15, 0), set);
+            assert_eq!(color(&ppu, 16, 0), clear);
+            assert_eq!(color(&ppu, 16, 7), set);
+        }
+    }
+
+    const COLORS: [u16; 4] = [WHITE, 0x001F, 0x03E0, 0x7C00];
+
+    //sets palette `palette` through BCPS/BCPD (0xFF68) or OCPS/OCPD (0xFF6A)
+    fn cgb_palette(ppu: &mut Ppu, spec: u16, palette: u8, colors: [u16; 4]) {
+        ppu.write(spec, 0x80 | (palette * 8));
+        for byte in colors.iter().flat_map(|color| color.to_le_bytes()) {
+            ppu.write(spec + 1, byte);
+        }
+    }
+
+    #[test]
+    fn palette_ram_auto_increments() {
+        let mut ppu = Ppu::new();
+        ppu.write(0xFF68, 0x80);
+        assert_eq!(ppu.read(0xFF68), 0xFF); //only in CGB mode
+        ppu.write(0xFF69, 0x12);
+        assert_eq!(ppu.bg_palettes[0], 0xFF);
+
+        ppu.set_cgb_mode(true);
+        ppu.write(0xFF68, 0xFE);
+        assert_eq!(ppu.read(0xFF68), 0xFE);
+        for data in [0x11, 0x22, 0x33] {
+            ppu.write(0xFF69, data);
+        }
+        assert_eq!(ppu.read(0xFF68), 0xC1); //wraps around
+        assert_eq!(ppu.bg_palettes[0x3E..], [0x11, 0x22]);
+        assert_eq!(ppu.bg_palettes[0], 0x33);
+        ppu.write(0xFF68, 0x3F);
+        assert_eq!(ppu.read(0xFF69), 0x22);
+        assert_eq!(ppu.read(0xFF68), 0x7F); //reads do not move the index
+
+        //without bit 7 the index stays put
+        ppu.write(0xFF6A, 0x05);
+        ppu.write(0xFF6B, 0x44);
+        ppu.write(0xFF6B, 0x55);
+        assert_eq!(ppu.read(0xFF6A), 0x45);
+        assert_eq!(ppu.read(0xFF6B), 0x55);
+        assert_eq!(ppu.obj_palettes[6], 0xFF);
+    }
+
+    #[test]
+    fn cgb_priorities() {
+        const OBJ_COLORS: [u16; 4] = [0, 0x0421, 0x0842, 0x0C63];
+        for renderer in [Renderer::Scanline, Renderer::Fifo] {
+            let mut ppu = with_sprite_tiles(0);
+            ppu.set_cgb_mode(true);
+            ppu.set_renderer(renderer);
+            cgb_palette(&mut ppu, 0xFF68, 0, COLORS);
+            cgb_palette(&mut ppu, 0xFF68, 1, [0x1111, 0x2222, 0x3333, 0x4444]);
+            cgb_palette(&mut ppu, 0xFF6A, 0, OBJ_COLORS);
+            cgb_palette(&mut ppu, 0xFF6A, 3, OBJ_COLORS.map(|color| color << 1));
+            //tile 2 in bank 1 is all color 1
+            for row in 0..8 {
+                ppu.vram1[0x20 + row * 2] = 0xFF;
+            }
+
+            //the first sprite in OAM wins, whatever the X
+            sprite(&mut ppu, 0, 16, 12, 2, 3);
+            sprite(&mut ppu, 1, 16, 8, 2, 0);
+            sprite(&mut ppu, 2, 32, 40, 2, 1 << ATTR_BANK | 3);
+            //over a color 1 tile and a color 0 tile, both with BG priority
+            ppu.vram[MAP_9800 + 4 * 32 + 8] = 1;
+            ppu.vram1[MAP_9800 + 4 * 32 + 8] = 1 << BG_ATTR_PRIORITY | 1;
+            ppu.vram1[MAP_9800 + 4 * 32 + 9] = 1 << BG_ATTR_PRIORITY;
+            sprite(&mut ppu, 3, 48, 72, 2, 0);
+            sprite(&mut ppu, 4, 48, 80, 2, 0);
+            ppu.write(0xFF40, 0x93);
+            run(&mut ppu, FIRST_FRAME);
+
+            assert_eq!(color(&ppu, 4, 0), OBJ_COLORS[3]);
+            assert_eq!(color(&ppu, 5, 0), OBJ_COLORS[3] << 1);
+            assert_eq!(color(&ppu, 9, 0), OBJ_COLORS[3] << 1);
+            assert_eq!(color(&ppu, 33, 16), OBJ_COLORS[1] << 1);
+            assert_eq!(color(&ppu, 65, 32), 0x2222);
+            assert_eq!(color(&ppu, 73, 32), OBJ_COLORS[3]);
+
+            //LCDC bit 0 clear puts sprites on top, but the BG still shows
+            ppu.write(0xFF40, 0x92);
+            run(&mut ppu, 154 * 456);
+            assert_eq!(color(&ppu, 64, 32), 0x2222);
+            assert_eq!(color(&ppu, 65, 32), OBJ_COLORS[3]);
+        }
+    }
+
+    #[test]
+    fn fifo_draws_the_same_frames_in_cgb_mode() {
+        let cgb_scene = |renderer| {
+            let mut ppu = scene(renderer);
+            ppu.set_cgb_mode(true);
+            for (i, attributes) in ppu.vram1[MAP_9800..].iter_mut().enumerate() {
+                *attributes = (i * 37 % 256) as u8;
+            }
+            for (i, byte) in ppu.vram1[..0x40].iter_mut().enumerate() {
+                *byte = (i * 91 % 256) as u8;
+            }
+            for index in 0..40 {
+                ppu.oam[index * 4 + 3] = (index * 53 % 256) as u8;
+            }
+            for palette in 0..8 {
+                let colors = [0, 1, 2, 3].map(|color| palette * 0x0C63 + color * 0x1234);
+                cgb_palette(&mut ppu, 0xFF68, palette as u8, colors);
+                cgb_palette(&mut ppu, 0xFF6A, palette as u8, colors.map(|color| !color));
+            }
+            ppu
+        };
+        let mut scanline = cgb_scene(Renderer::Scanline);
+        let mut fifo = cgb_scene(Renderer::Fifo);
+        for _ in 0..3 {
+            run(&mut scanline, 154 * 456);
+            run(&mut fifo, 154 * 456);
+            assert!(scanline.frame() == fifo.frame());
+        }
+    }
+
+    #[test]
+    fn vbk_only_switches_in_cgb_mode() {
+        let mut ppu = Ppu::new();
+        ppu.write(0xFF4F, 1);
+        assert_eq!(ppu.read(0xFF4F), 0xFF);
+        ppu.cpu_vram_mut()[0] = 0x12;
+        assert_eq!(ppu.vram[0], 0x12);
+
+        ppu.set_cgb_mode(true);
+        ppu.write(0xFF4F, 0xFF);
+        assert_eq!(ppu.read(0xFF4F), 0xFF);
+        ppu.cpu_vram_mut()[0] = 0x34;
+        assert_eq!((ppu.vram[0], ppu.vram1[0]), (0x12, 0x34));
+        ppu.write(0xFF4F, 0);
+        assert_eq!(ppu.read(0xFF4F), 0xFE);
+        assert_eq!(ppu.cpu_vram()[0], 0x12);
+    }
+
+    #[test]
+    fn lcd_off_blanks_the_screen_until_a_frame_is_shown() {
+        let mut ppu = with_tile(0x91);
+        ppu.log_hazards(true);
+        ppu.vram[MAP_9800] = 1;
+        run(&mut ppu, FIRST_FRAME);
+        assert_eq!(pixel(&ppu, 0, 0), 1);
+
+        run(&mut ppu, 5 * 456); //VBlank is fine
+        ppu.write(0xFF40, 0x11);
+        assert!(ppu.take_hazards().is_empty());
+        assert!(ppu.frame().iter().all(|&color| color == WHITE));
+
+        //the first frame is drawn but not shown
+        ppu.write(0xFF40, 0x91);
+        run(&mut ppu, 154 * 456);
+        assert_eq!(pixel(&ppu, 0, 0), 0);
+        run(&mut ppu, 144 * 456);
+        assert_eq!(pixel(&ppu, 0, 0), 1);
+    }
+
+    #[test]
+    fn lcd_off_outside_vblank_is_a_hazard() {
+        let mut ppu = enabled();
+        ppu.log_hazards(true);
+        run(&mut ppu, 20 * 456 + 100);
+        ppu.write(0xFF40, 0x00);
+        assert_eq!(ppu.take_hazards(), [LcdOffHazard { ly: 20, dot: 100 }]);
+        assert_eq!(
+            LcdOffHazard { ly: 20, dot: 100 }.to_string(),
+            "LCD turned off outside VBlank at LY 20 dot 100"
+        );
+        assert!(ppu.take_hazards().is_empty());
+
+        //nothing piles up unless asked for
+        ppu.log_hazards(false);
+        ppu.write(0xFF40, 0x80);
+        run(&mut ppu, 456);
+        ppu.write(0xFF40, 0x00);
+        assert!(ppu.hazards.is_none());
+        assert!(ppu.take_hazards().is_empty());
+    }
+
+    #[test]
+    fn lcd_off_holds_ly() {
+        let mut ppu = enabled();
+        run(&mut ppu, 3 * 456);
+        ppu.write(0xFF40, 0x00);
+        assert_eq!(ppu.ly(), 0);
+        assert_eq!(run(&mut ppu, 200 * 456), 0);
+        assert_eq!(ppu.ly(), 0);
+        assert_eq!(ppu.read(0xFF41) & 0b11, 0);
+    }
+}
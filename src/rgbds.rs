@@ -0,0 +1,348 @@
+//! Exports a ROM as RGBDS source that assembles back to the same bytes: one file and one
+//! `SECTION` per bank, code from `RomDisassembly`, everything else as `db`, and a `hardware.inc`
+//! with the register names.
+//!
+//! Needs RGBDS 0.6 or newer, older versions insert a NOP after HALT and turn some loads into LDH.
+//! Anything that could still assemble differently is written as `db` with the instruction in a
+//! comment.
+
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::disasm::{Instruction, Operand};
+use crate::opcode::{Op8, OpcodeUnprefixed};
+use crate::rom_disasm::{bank_and_addr, RomDisassembly, BANK_SIZE};
+
+pub const HARDWARE_REGISTERS: [(u16, &str); 53] = [
+    (0xFF00, "rP1"),
+    (0xFF01, "rSB"),
+    (0xFF02, "rSC"),
+    (0xFF04, "rDIV"),
+    (0xFF05, "rTIMA"),
+    (0xFF06, "rTMA"),
+    (0xFF07, "rTAC"),
+    (0xFF0F, "rIF"),
+    (0xFF10, "rNR10"),
+    (0xFF11, "rNR11"),
+    (0xFF12, "rNR12"),
+    (0xFF13, "rNR13"),
+    (0xFF14, "rNR14"),
+    (0xFF16, "rNR21"),
+    (0xFF17, "rNR22"),
+    (0xFF18, "rNR23"),
+    (0xFF19, "rNR24"),
+    (0xFF1A, "rNR30"),
+    (0xFF1B, "rNR31"),
+    (0xFF1C, "rNR32"),
+    (0xFF1D, "rNR33"),
+    (0xFF1E, "rNR34"),
+    (0xFF20, "rNR41"),
+    (0xFF21, "rNR42"),
+    (0xFF22, "rNR43"),
+    (0xFF23, "rNR44"),
+    (0xFF24, "rNR50"),
+    (0xFF25, "rNR51"),
+    (0xFF26, "rNR52"),
+    (0xFF40, "rLCDC"),
+    (0xFF41, "rSTAT"),
+    (0xFF42, "rSCY"),
+    (0xFF43, "rSCX"),
+    (0xFF44, "rLY"),
+    (0xFF45, "rLYC"),
+    (0xFF46, "rDMA"),
+    (0xFF47, "rBGP"),
+    (0xFF48, "rOBP0"),
+    (0xFF49, "rOBP1"),
+    (0xFF4A, "rWY"),
+    (0xFF4B, "rWX"),
+    (0xFF4D, "rKEY1"),
+    (0xFF4F, "rVBK"),
+    (0xFF51, "rHDMA1"),
+    (0xFF52, "rHDMA2"),
+    (0xFF53, "rHDMA3"),
+    (0xFF54, "rHDMA4"),
+    (0xFF55, "rHDMA5"),
+    (0xFF56, "rRP"),
+    (0xFF68, "rBCPS"),
+    (0xFF69, "rBCPD"),
+    (0xFF6A, "rOCPS"),
+    (0xFF6B, "rOCPD"),
+];
+
+//bytes per `db` line
+const DATA_LINE: usize = 16;
+
+fn register_name(addr: u16) -> Option<&'static str> {
+    HARDWARE_REGISTERS
+        .iter()
+        .find(|&&(reg, _)| reg == addr)
+        .map(|&(_, name)| name)
+}
+
+fn hardware_inc() -> String {
+    let mut out = String::from("; generated by anotherboy\n\n");
+    for (addr, name) in HARDWARE_REGISTERS.iter() {
+        writeln!(out, "DEF {} EQU ${:04X}", name, addr).unwrap();
+    }
+    out
+}
+
+pub fn bank_file_name(bank: usize) -> String {
+    format!("bank_{:02X}.asm", bank)
+}
+
+//instructions rgbasm might encode differently from the original bytes
+fn needs_db(instruction: &Instruction, bytes: &[u8]) -> bool {
+    match instruction.unprefixed() {
+        Some(OpcodeUnprefixed::Illegal) => true,
+        //STOP is always assembled with a 0x00 after it
+        Some(OpcodeUnprefixed::Stop) => bytes[1] != 0x00,
+        //could be optimized to LDH
+        Some(OpcodeUnprefixed::Ld8(Op8::AddrU16, Op8::A))
+        | Some(OpcodeUnprefixed::Ld8(Op8::A, Op8::AddrU16)) => {
+            matches!(instruction.operand, Some(Operand::U16(addr)) if addr >= 0xFF00)
+        }
+        _ => false,
+    }
+}
+
+/// The instruction in rgbasm syntax, with `target` in place of a jump or call address.
+fn rgbds_syntax(instruction: &Instruction, target: Option<&str>) -> String {
+    let text = instruction
+        .mnemonic()
+        .to_ascii_lowercase()
+        .replace('(', "[")
+        .replace(')', "]")
+        .replace(',', ", ");
+
+    if let Some(OpcodeUnprefixed::Rst(vector)) = instruction.unprefixed() {
+        return format!("rst ${:02X}", vector);
+    }
+    if let Some(OpcodeUnprefixed::Stop) = instruction.unprefixed() {
+        return "stop".to_string();
+    }
+    //LD A,(C) and LD (C),A
+    let text = text
+        .replace("ld [c]", "ldh [c]")
+        .replace("ld a, [c]", "ldh a, [c]");
+
+    match instruction.operand {
+        None => text,
+        Some(Operand::U8(value)) => text.replace("u8", &format!("${:02X}", value)),
+        Some(Operand::U16(value)) => {
+            let value = format!("${:04X}", value);
+            text.replace("u16", target.unwrap_or(&value))
+        }
+        Some(Operand::HighAddr(addr)) => {
+            let value = format!("${:04X}", addr);
+            text.replace("a8", register_name(addr).unwrap_or(&value))
+        }
+        Some(Operand::Relative(addr)) => {
+            let value = format!("${:04X}", addr);
+            text.replace("r8", target.unwrap_or(&value))
+        }
+        Some(Operand::I8(offset)) => {
+            if text.contains("+r8") {
+                text.replace("+r8", &format!("{:+}", offset))
+            } else {
+                text.replace("r8", &offset.to_string())
+            }
+        }
+    }
+}
+
+fn write_data(out: &mut String, bytes: &[u8]) {
+    for chunk in bytes.chunks(DATA_LINE) {
+        let bytes: Vec<String> = chunk.iter().map(|byte| format!("${:02X}", byte)).collect();
+        writeln!(out, "    db {}", bytes.join(", ")).unwrap();
+    }
+}
+
+/// The source files for `rom`, as (file name, contents).
+pub fn export(rom: &[u8]) -> Vec<(String, String)> {
+    let disassembly = RomDisassembly::new(rom);
+    //labels inside an instruction cannot be written, jumps there use the address
+    let label = |offset: usize| {
+        if disassembly.is_inside_instruction(offset) {
+            None
+        } else {
+            disassembly.label_name(offset)
+        }
+    };
+
+    let mut files = vec![("hardware.inc".to_string(), hardware_inc())];
+    for bank_start in (0..rom.len()).step_by(BANK_SIZE) {
+        let bank = bank_start / BANK_SIZE;
+        let bank_end = (bank_start + BANK_SIZE).min(rom.len());
+
+        let mut out = String::from("INCLUDE \"hardware.inc\"\n\n");
+        if bank == 0 {
+            writeln!(out, "SECTION \"ROM Bank $00\", ROM0[$0000]").unwrap();
+        } else {
+            writeln!(
+                out,
+                "SECTION \"ROM Bank ${:02X}\", ROMX[$4000], BANK[${:02X}]",
+                bank, bank
+            )
+            .unwrap();
+        }
+
+        let mut offset = bank_start;
+        let mut data_start = None;
+        while offset < bank_end {
+            let instruction = disassembly.instruction(offset);
+            let name = label(offset);
+            if instruction.is_some() || name.is_some() {
+                if let Some(start) = data_start.take() {
+                    write_data(&mut out, &rom[start..offset]);
+                }
+            }
+            if let Some(name) = name {
+                writeln!(out, "{}::", name).unwrap();
+            }
+
+            match instruction {
+                Some(instruction) => {
+                    let len = usize::from(instruction.len);
+                    let bytes = &rom[offset..offset + len];
+                    if needs_db(instruction, bytes) {
+                        let bytes: Vec<String> =
+                            bytes.iter().map(|byte| format!("${:02X}", byte)).collect();
+                        writeln!(out, "    db {} ; {}", bytes.join(", "), instruction).unwrap();
+                    } else {
+                        let target = disassembly.target(offset).and_then(label);
+                        writeln!(out, "    {}", rgbds_syntax(instruction, target.as_deref()))
+                            .unwrap();
+                    }
+                    offset += len;
+                }
+                None => {
+                    data_start.get_or_insert(offset);
+                    offset += 1;
+                }
+            }
+        }
+        if let Some(start) = data_start {
+            write_data(&mut out, &rom[start..bank_end]);
+        }
+
+        files.push((bank_file_name(bank), out));
+    }
+    files
+}
+
+/// Writes the source files for `rom` into `dir`.
+pub fn export_to(rom: &[u8], dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+    for (name, contents) in export(rom) {
+        let path = dir.join(name);
+        std::fs::write(&path, contents).with_context(|| format!("writing {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rom_disasm::rom_offset;
+    use std::process::Command;
+
+    fn rom() -> Vec<u8> {
+        let mut rom = vec![0xFF; 2 * BANK_SIZE];
+        rom[0x100..0x104].copy_from_slice(&[0x00, 0xC3, 0x50, 0x01]); //NOP; JP $0150
+        rom[0x104..0x150].fill(0x00);
+        rom[0x150..0x16A].copy_from_slice(&[
+            0xF0, 0x44, //LDH A,(rLY)
+            0xEA, 0x44, 0xFF, //LD ($FF44),A
+            0x3E, 0x01, //LD A,$01
+            0xEA, 0x00, 0x20, //LD ($2000),A
+            0xCD, 0x00, 0x40, //CALL $4000
+            0x10, 0x01, //STOP with a non-zero byte
+            0xF8, 0xFE, //LD HL,SP-2
+            0xE2, //LD (C),A
+            0xCB, 0x7E, //BIT 7,(HL)
+            0x76, //HALT
+            0x20, 0xE9, //JR NZ,$0150
+            0x18, 0xFF, //JR into its own operand, which is RST $38
+            0xD3, //illegal
+        ]);
+        rom[rom_offset(1, 0x4000)..rom_offset(1, 0x4002)].copy_from_slice(&[0xE8, 0x05]); //ADD SP,5
+        rom[rom_offset(1, 0x4002)] = 0xC9; //RET
+        rom
+    }
+
+    #[test]
+    fn writes_rgbds_syntax() {
+        let files = export(&rom());
+        let names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["hardware.inc", "bank_00.asm", "bank_01.asm"]);
+        assert!(files[0].1.contains("DEF rLY EQU $FF44\n"));
+
+        let bank0 = &files[1].1;
+        for line in [
+            "SECTION \"ROM Bank $00\", ROM0[$0000]\n",
+            "Entry::\n    nop\n    jp Jump_00_0150\n    db $00, $00",
+            "Jump_00_0150::\n    ldh a, [rLY]\n",
+            "    db $EA, $44, $FF ; LD ($FF44),A\n",
+            "    call Call_01_4000\n",
+            "    db $10, $01 ; STOP $01\n",
+            "    ld hl, sp-2\n    ldh [c], a\n    bit 7, [hl]\n    halt\n",
+            "    jr nz, Jump_00_0150\n    jr $0168\n",
+            "    db $D3 ; ILLEGAL_D3\n",
+        ] {
+            assert!(bank0.contains(line), "missing {:?} in\n{}", line, bank0);
+        }
+
+        let bank1 = &files[2].1;
+        assert!(bank1.contains("SECTION \"ROM Bank $01\", ROMX[$4000], BANK[$01]\n"));
+        assert!(bank1.contains("Call_01_4000::\n    add sp, 5\n    ret\n"));
+    }
+
+    fn have_rgbds() -> bool {
+        ["rgbasm", "rgblink"].iter().all(|tool| {
+            Command::new(tool)
+                .arg("-V")
+                .output()
+                .is_ok_and(|output| output.status.success())
+        })
+    }
+
+    /// Assembles the export with rgbasm/rgblink and compares it with the original. Skipped when
+    /// RGBDS is not installed.
+    #[test]
+    fn round_trip() -> Result<()> {
+        if !have_rgbds() {
+            eprintln!("skipping RGBDS round trip, rgbasm/rgblink not found");
+            return Ok(());
+        }
+
+        let rom = rom();
+        let dir = std::env::temp_dir().join(format!("anotherboy-rgbds-{}", std::process::id()));
+        export_to(&rom, &dir)?;
+
+        let mut objects = Vec::new();
+        for bank in 0..rom.len() / BANK_SIZE {
+            let source = bank_file_name(bank);
+            let object = source.replace(".asm", ".o");
+            let status = Command::new("rgbasm")
+                .current_dir(&dir)
+                .args(["-o", &object, &source])
+                .status()?;
+            assert!(status.success(), "rgbasm failed on {}", source);
+            objects.push(object);
+        }
+        let status = Command::new("rgblink")
+            .current_dir(&dir)
+            .args(["-o", "out.gb"])
+            .args(&objects)
+            .status()?;
+        assert!(status.success(), "rgblink failed");
+
+        let rebuilt = std::fs::read(dir.join("out.gb"))?;
+        std::fs::remove_dir_all(&dir)?;
+        assert!(rebuilt == rom, "rebuilt ROM differs");
+        Ok(())
+    }
+}
@@ -0,0 +1,464 @@
+//! Disassembles a whole ROM by following control flow from the entry point and the RST and
+//! interrupt vectors, so code and data are kept apart.
+//!
+//! Bank 0 is always at 0x0000-0x3FFF and the switchable bank at 0x4000-0x7FFF. To know which bank
+//! a jump into 0x4000-0x7FFF lands in, `LD A,u8` followed by `LD (u16),A` to 0x2000-0x3FFF is
+//! tracked along each path. Jumps from bank 0 without such a write are reported as unresolved.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use crate::disasm::{Instruction, OpInfo, Operand};
+use crate::opcode::{Condition, Op16, Op8, OpcodePrefixed, OpcodeUnprefixed};
+
+pub const BANK_SIZE: usize = 0x4000;
+
+const RST_VECTORS: [u16; 8] = [0x00, 0x08, 0x10, 0x18, 0x20, 0x28, 0x30, 0x38];
+const INTERRUPTS: [(u16, &str); 5] = [
+    (0x40, "VBlankInterrupt"),
+    (0x48, "StatInterrupt"),
+    (0x50, "TimerInterrupt"),
+    (0x58, "SerialInterrupt"),
+    (0x60, "JoypadInterrupt"),
+];
+const ENTRY_POINT: u16 = 0x0100;
+
+//the ROM bank select register of MBC1/3/5
+const BANK_SELECT: std::ops::RangeInclusive<u16> = 0x2000..=0x3FFF;
+
+//bytes per `db` line
+const DATA_LINE: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Label {
+    Named(String), //entry point and vectors
+    Call,
+    Jump,
+}
+
+/// A jump or call whose destination could not be placed in a ROM bank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unresolved {
+    pub from: usize, //ROM offset of the instruction
+    pub target: u16,
+}
+
+pub struct RomDisassembly<'a> {
+    rom: &'a [u8],
+    code: BTreeMap<usize, Instruction>, //by ROM offset
+    targets: BTreeMap<usize, usize>,    //ROM offset of each resolved jump/call target
+    labels: BTreeMap<usize, Label>,
+    pub unresolved: Vec<Unresolved>,
+}
+
+//where the CPU is: a ROM offset plus the bank it believes is mapped at 0x4000
+#[derive(Clone, Copy)]
+struct Path {
+    offset: usize,
+    mapped_bank: Option<usize>,
+}
+
+pub fn rom_offset(bank: usize, addr: u16) -> usize {
+    if addr < 0x4000 {
+        usize::from(addr)
+    } else {
+        bank * BANK_SIZE + usize::from(addr - 0x4000)
+    }
+}
+
+pub fn bank_and_addr(offset: usize) -> (usize, u16) {
+    let bank = offset / BANK_SIZE;
+    let addr = if bank == 0 {
+        offset as u16
+    } else {
+        (0x4000 + offset % BANK_SIZE) as u16
+    };
+    (bank, addr)
+}
+
+//whether the instruction leaves something unknown in A
+fn writes_a(instruction: &Instruction) -> bool {
+    match instruction.info {
+        OpInfo::Unprefixed(info) => matches!(
+            info.inst,
+            OpcodeUnprefixed::Ld8(Op8::A, _)
+                | OpcodeUnprefixed::Inc8(Op8::A)
+                | OpcodeUnprefixed::Dec8(Op8::A)
+                | OpcodeUnprefixed::Add8(_)
+                | OpcodeUnprefixed::Adc(_)
+                | OpcodeUnprefixed::Sub(_)
+                | OpcodeUnprefixed::Sbc(_)
+                | OpcodeUnprefixed::And(_)
+                | OpcodeUnprefixed::Xor(_)
+                | OpcodeUnprefixed::Or(_)
+                | OpcodeUnprefixed::Daa
+                | OpcodeUnprefixed::Cpl
+                | OpcodeUnprefixed::Rlca
+                | OpcodeUnprefixed::Rrca
+                | OpcodeUnprefixed::Rla
+                | OpcodeUnprefixed::Rra
+                | OpcodeUnprefixed::Pop(Op16::AF)
+                | OpcodeUnprefixed::Call(_)
+                | OpcodeUnprefixed::Rst(_)
+        ),
+        OpInfo::Prefixed(info) => match info.inst {
+            OpcodePrefixed::Bit(_, _) => false,
+            OpcodePrefixed::Rlc(target)
+            | OpcodePrefixed::Rrc(target)
+            | OpcodePrefixed::Rl(target)
+            | OpcodePrefixed::Rr(target)
+            | OpcodePrefixed::Sla(target)
+            | OpcodePrefixed::Sra(target)
+            | OpcodePrefixed::Srl(target)
+            | OpcodePrefixed::Swap(target)
+            | OpcodePrefixed::Res(_, target)
+            | OpcodePrefixed::Set(_, target) => target == Op8::A,
+        },
+    }
+}
+
+impl<'a> RomDisassembly<'a> {
+    pub fn new(rom: &'a [u8]) -> Self {
+        let mut disassembly = RomDisassembly {
+            rom,
+            code: BTreeMap::new(),
+            targets: BTreeMap::new(),
+            labels: BTreeMap::new(),
+            unresolved: Vec::new(),
+        };
+
+        let mut entries = vec![(ENTRY_POINT, "Entry".to_string())];
+        entries.extend(
+            RST_VECTORS
+                .iter()
+                .map(|&addr| (addr, format!("Rst_{:02X}", addr))),
+        );
+        entries.extend(
+            INTERRUPTS
+                .iter()
+                .map(|&(addr, name)| (addr, name.to_string())),
+        );
+
+        for (addr, name) in entries {
+            if usize::from(addr) < rom.len() {
+                disassembly
+                    .labels
+                    .insert(usize::from(addr), Label::Named(name));
+                disassembly.follow(Path {
+                    offset: usize::from(addr),
+                    mapped_bank: None,
+                });
+            }
+        }
+        disassembly
+    }
+
+    fn num_banks(&self) -> usize {
+        self.rom.len().div_ceil(BANK_SIZE)
+    }
+
+    fn add_label(&mut self, offset: usize, label: Label) {
+        let entry = self.labels.entry(offset).or_insert(Label::Jump);
+        if *entry == Label::Jump {
+            *entry = label;
+        }
+    }
+
+    //the ROM offset `target` refers to when jumped to from `path`
+    fn resolve(&self, path: &Path, target: u16) -> Option<usize> {
+        let (bank, _) = bank_and_addr(path.offset);
+        let offset = match target {
+            0x0000..=0x3FFF => usize::from(target),
+            0x4000..=0x7FFF => {
+                //code in a switchable bank can rely on its own bank being mapped
+                let bank = path.mapped_bank.or((bank > 0).then_some(bank))?;
+                rom_offset(bank, target)
+            }
+            _ => return None, //RAM
+        };
+        if offset < self.rom.len() {
+            Some(offset)
+        } else {
+            None
+        }
+    }
+
+    fn follow(&mut self, start: Path) {
+        let mut pending = vec![start];
+        while let Some(mut path) = pending.pop() {
+            let mut a = None; //known value of A
+            loop {
+                if self.code.contains_key(&path.offset) {
+                    break;
+                }
+                let (bank, addr) = bank_and_addr(path.offset);
+                let bank_end = ((bank + 1) * BANK_SIZE).min(self.rom.len());
+                let instruction = match Instruction::decode(&self.rom[path.offset..bank_end], addr)
+                {
+                    Some(instruction) => instruction,
+                    None => break,
+                };
+                self.code.insert(path.offset, instruction);
+
+                let inst = instruction.unprefixed();
+                if let Some(target) = instruction.target() {
+                    match self.resolve(&path, target) {
+                        Some(offset) => {
+                            let label = match inst {
+                                Some(OpcodeUnprefixed::Call(_)) => Label::Call,
+                                _ => Label::Jump,
+                            };
+                            self.add_label(offset, label);
+                            self.targets.insert(path.offset, offset);
+                            let mapped_bank = path.mapped_bank.or((bank > 0).then_some(bank));
+                            pending.push(Path {
+                                offset,
+                                mapped_bank,
+                            });
+                        }
+                        None if target < 0x8000 => self.unresolved.push(Unresolved {
+                            from: path.offset,
+                            target,
+                        }),
+                        None => {}
+                    }
+                }
+
+                match inst {
+                    Some(OpcodeUnprefixed::Ld8(Op8::A, Op8::U8)) => {
+                        a = match instruction.operand {
+                            Some(Operand::U8(value)) => Some(value),
+                            _ => None,
+                        };
+                    }
+                    Some(OpcodeUnprefixed::Ld8(Op8::AddrU16, Op8::A)) => {
+                        if let (Some(Operand::U16(dest)), Some(value)) = (instruction.operand, a) {
+                            if BANK_SELECT.contains(&dest) {
+                                let value = usize::from(value).max(1);
+                                path.mapped_bank = Some(value % self.num_banks());
+                            }
+                        }
+                    }
+                    _ if writes_a(&instruction) => a = None,
+                    _ => {}
+                }
+
+                let ends_path = matches!(
+                    inst,
+                    Some(OpcodeUnprefixed::Jp(Condition::Unconditional, _))
+                        | Some(OpcodeUnprefixed::Jr(Condition::Unconditional))
+                        | Some(OpcodeUnprefixed::Ret(Condition::Unconditional))
+                        | Some(OpcodeUnprefixed::Reti)
+                        | Some(OpcodeUnprefixed::Illegal)
+                );
+                if ends_path {
+                    break;
+                }
+                path.offset += usize::from(instruction.len);
+            }
+        }
+    }
+
+    pub fn is_code(&self, offset: usize) -> bool {
+        self.code.contains_key(&offset)
+    }
+
+    pub fn instruction(&self, offset: usize) -> Option<&Instruction> {
+        self.code.get(&offset)
+    }
+
+    pub fn label_name(&self, offset: usize) -> Option<String> {
+        let (bank, addr) = bank_and_addr(offset);
+        let name = match self.labels.get(&offset)? {
+            Label::Named(name) => name.clone(),
+            Label::Call => format!("Call_{:02X}_{:04X}", bank, addr),
+            Label::Jump => format!("Jump_{:02X}_{:04X}", bank, addr),
+        };
+        Some(name)
+    }
+
+    /// The label of the instruction's jump or call target, if it has one.
+    pub fn target_label(&self, offset: usize) -> Option<String> {
+        self.label_name(self.target(offset)?)
+    }
+
+    //ROM offset the instruction at `offset` jumps or calls to, when known
+    pub fn target(&self, offset: usize) -> Option<usize> {
+        self.targets.get(&offset).copied()
+    }
+
+    //a label can point into the middle of an instruction when code jumps there
+    pub fn is_inside_instruction(&self, offset: usize) -> bool {
+        self.code
+            .range(..offset)
+            .next_back()
+            .is_some_and(|(&start, instruction)| start + usize::from(instruction.len) > offset)
+    }
+
+    /// Offsets of the runs of data bytes, split at banks, code and labels.
+    pub fn data_runs(&self) -> Vec<std::ops::Range<usize>> {
+        let mut runs = Vec::new();
+        let mut offset = 0;
+        while offset < self.rom.len() {
+            if let Some(instruction) = self.code.get(&offset) {
+                offset += usize::from(instruction.len);
+                continue;
+            }
+            let start = offset;
+            offset += 1;
+            while offset < self.rom.len()
+                && !offset.is_multiple_of(BANK_SIZE)
+                && !self.code.contains_key(&offset)
+                && !self.labels.contains_key(&offset)
+            {
+                offset += 1;
+            }
+            runs.push(start..offset);
+        }
+        runs
+    }
+
+    /// A listing of every bank, with code as instructions and everything else as `db` lines.
+    pub fn listing(&self) -> String {
+        let unresolved: BTreeSet<usize> = self.unresolved.iter().map(|u| u.from).collect();
+        let data: BTreeMap<usize, usize> = self
+            .data_runs()
+            .into_iter()
+            .map(|run| (run.start, run.end))
+            .collect();
+
+        let mut out = String::new();
+        let mut offset = 0;
+        while offset < self.rom.len() {
+            let (bank, addr) = bank_and_addr(offset);
+            if offset.is_multiple_of(BANK_SIZE) {
+                if offset > 0 {
+                    out.push('\n');
+                }
+                writeln!(out, "; bank {:02X}", bank).unwrap();
+            }
+            if let Some(name) = self.label_name(offset) {
+                writeln!(out, "{}:", name).unwrap();
+            }
+
+            if let Some(instruction) = self.code.get(&offset) {
+                let len = usize::from(instruction.len);
+                let bytes: Vec<String> = self.rom[offset..offset + len]
+                    .iter()
+                    .map(|byte| format!("{:02X}", byte))
+                    .collect();
+                let text = match self.target_label(offset) {
+                    Some(label) => instruction.with_label(&label),
+                    None => instruction.to_string(),
+                };
+                write!(
+                    out,
+                    "    {:02X}:{:04X}  {:<8}  {}",
+                    bank,
+                    addr,
+                    bytes.join(" "),
+                    text
+                )
+                .unwrap();
+                if unresolved.contains(&offset) {
+                    out.push_str("  ; target bank unknown");
+                }
+                out.push('\n');
+                offset += len;
+            } else {
+                let end = data[&offset];
+                for chunk_start in (offset..end).step_by(DATA_LINE) {
+                    let chunk_end = (chunk_start + DATA_LINE).min(end);
+                    let bytes: Vec<String> = self.rom[chunk_start..chunk_end]
+                        .iter()
+                        .map(|byte| format!("${:02X}", byte))
+                        .collect();
+                    let (_, addr) = bank_and_addr(chunk_start);
+                    writeln!(out, "    {:02X}:{:04X}  db {}", bank, addr, bytes.join(",")).unwrap();
+                }
+                offset = end;
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rom() -> Vec<u8> {
+        let mut rom = vec![0xFF; 4 * BANK_SIZE];
+        rom[0x00] = 0xC9; //RET
+        rom[0x100..0x104].copy_from_slice(&[0x00, 0xC3, 0x50, 0x01]); //NOP; JP $0150
+        rom[0x104..0x150].fill(0x00);
+        rom[0x150..0x15C].copy_from_slice(&[
+            0x3E, 0x02, //LD A,$02
+            0xEA, 0x00, 0x20, //LD ($2000),A
+            0xCD, 0x00, 0x40, //CALL $4000
+            0xC3, 0x10, 0x40, //JP $4010 (still bank 2)
+            0x00,
+        ]);
+        rom[rom_offset(2, 0x4000)] = 0xC9; //RET
+        rom[rom_offset(2, 0x4010)..rom_offset(2, 0x4013)].copy_from_slice(&[0x18, 0xFE, 0x00]); //JR $4010
+        rom
+    }
+
+    #[test]
+    fn follows_bank_switches() {
+        let rom = rom();
+        let disassembly = RomDisassembly::new(&rom);
+
+        assert!(disassembly.is_code(0x150));
+        assert!(disassembly.is_code(rom_offset(2, 0x4000)));
+        assert!(disassembly.is_code(rom_offset(2, 0x4010)));
+        assert!(!disassembly.is_code(0x104)); //header
+        assert!(!disassembly.is_code(rom_offset(1, 0x4000)));
+        assert!(disassembly.unresolved.is_empty());
+
+        assert_eq!(
+            disassembly.label_name(rom_offset(2, 0x4000)).as_deref(),
+            Some("Call_02_4000")
+        );
+        assert_eq!(
+            disassembly.label_name(0x150).as_deref(),
+            Some("Jump_00_0150")
+        );
+    }
+
+    #[test]
+    fn unknown_bank_is_unresolved() {
+        let mut rom = rom();
+        rom[0x150] = 0x00; //no LD A,u8 before the bank write
+        rom[0x151] = 0x00;
+        let disassembly = RomDisassembly::new(&rom);
+        assert_eq!(
+            disassembly.unresolved,
+            vec![
+                Unresolved {
+                    from: 0x155,
+                    target: 0x4000
+                },
+                Unresolved {
+                    from: 0x158,
+                    target: 0x4010
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn listing_separates_code_and_data() {
+        let rom = rom();
+        let listing = RomDisassembly::new(&rom).listing();
+        assert!(
+            listing.contains("Entry:\n    00:0100  00        NOP\n"),
+            "{}",
+            listing
+        );
+        assert!(listing.contains("00:0101  C3 50 01  JP Jump_00_0150\n"));
+        assert!(listing.contains("00:0155  CD 00 40  CALL Call_02_4000\n"));
+        assert!(listing.contains("00:0104  db $00,$00"));
+        assert!(listing.contains("Jump_02_4010:\n    02:4010  18 FE     JR Jump_02_4010\n"));
+    }
+}
@@ -0,0 +1,79 @@
+use crate::util::Twiddling;
+
+//8 bits at 8192 Hz on the internal clock
+const TRANSFER_M_CYCLES: u16 = 1024;
+
+/// The link port. There is never anything on the other end, so every byte shifted in is 0xFF,
+/// but everything shifted out is kept in `output`, which is how test ROMs report results.
+#[derive(Debug, Default)]
+pub struct Serial {
+    data: u8,    //SB
+    control: u8, //SC
+    remaining: u16,
+    pub output: Vec<u8>,
+}
+
+impl Serial {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advances the port by one M-cycle. Returns `true` when the serial interrupt should be
+    /// requested.
+    pub fn tick(&mut self) -> bool {
+        if self.remaining == 0 {
+            return false;
+        }
+
+        self.remaining -= 1;
+        if self.remaining == 0 {
+            self.data = 0xFF;
+            self.control.set_bit(7, false);
+            return true;
+        }
+        false
+    }
+
+    pub fn read(&self, addr: u16) -> u8 {
+        match addr {
+            0xFF01 => self.data,
+            0xFF02 => self.control | 0b0111_1110,
+            _ => unreachable!("{:#06X} is not a serial register", addr),
+        }
+    }
+
+    pub fn write(&mut self, addr: u16, data: u8) {
+        match addr {
+            0xFF01 => self.data = data,
+            0xFF02 => {
+                self.control = data & 0b1000_0001;
+                //only the internal clock makes progress without a partner
+                if self.control == 0b1000_0001 {
+                    self.output.push(self.data);
+                    self.remaining = TRANSFER_M_CYCLES;
+                }
+            }
+            _ => unreachable!("{:#06X} is not a serial register", addr),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transfer_completes_with_interrupt() {
+        let mut serial = Serial::new();
+        serial.write(0xFF01, b'P');
+        serial.write(0xFF02, 0x81);
+        assert_eq!(serial.output, b"P");
+
+        for _ in 1..TRANSFER_M_CYCLES {
+            assert!(!serial.tick());
+        }
+        assert!(serial.tick());
+        assert_eq!(serial.read(0xFF01), 0xFF);
+        assert_eq!(serial.read(0xFF02) & 0x80, 0);
+    }
+}
@@ -0,0 +1,262 @@
+//! Runs the single-step SM83 test vectors from https://github.com/SingleStepTests/sm83. There is
+//! one JSON file per opcode, each with thousands of cases giving the initial and final CPU state
+//! and the bus activity of every M-cycle.
+//!
+//! The vectors are not checked in. Point `SM83_TESTS_DIR` at the `v1` directory of a checkout
+//! (defaults to `tests/sm83/v1`), otherwise the suite is skipped.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::cpu::{BusActivity, Cpu, CpuRam, Registers};
+
+const DEFAULT_DIR: &str = "tests/sm83/v1";
+
+//failing cases printed per file, the rest are only counted
+const REPORTED_FAILURES: usize = 3;
+
+#[derive(Deserialize)]
+struct TestCase {
+    name: String,
+    initial: State,
+    #[serde(rename = "final")]
+    expected: State,
+    cycles: Vec<Value>,
+}
+
+#[derive(Deserialize)]
+struct State {
+    pc: u16,
+    sp: u16,
+    a: u8,
+    b: u8,
+    c: u8,
+    d: u8,
+    e: u8,
+    f: u8,
+    h: u8,
+    l: u8,
+    ime: Option<u8>,
+    ie: Option<u8>,
+    ram: Vec<(u16, u8)>,
+}
+
+impl State {
+    fn registers(&self) -> Registers {
+        let mut registers = Registers::default();
+        registers.set_af(u16::from_be_bytes([self.a, self.f]));
+        registers.bc.set_both(u16::from_be_bytes([self.b, self.c]));
+        registers.de.set_both(u16::from_be_bytes([self.d, self.e]));
+        registers.hl.set_both(u16::from_be_bytes([self.h, self.l]));
+        registers.sp = self.sp;
+        registers.pc = self.pc;
+        registers
+    }
+}
+
+//cycles are `[addr, data, "r-m"]`, `[addr, data, "-wm"]`, or null/"---" when the bus is idle
+fn parse_cycle(cycle: &Value) -> BusActivity {
+    let parts = (
+        cycle.get(0).and_then(Value::as_u64),
+        cycle.get(1).and_then(Value::as_u64),
+        cycle.get(2).and_then(Value::as_str),
+    );
+
+    match parts {
+        (Some(addr), Some(data), Some(kind)) if kind.starts_with('r') => {
+            BusActivity::Read(addr as u16, data as u8)
+        }
+        (Some(addr), Some(data), Some(kind)) if kind.contains('w') => {
+            BusActivity::Write(addr as u16, data as u8)
+        }
+        _ => BusActivity::Idle,
+    }
+}
+
+fn check(report: &mut String, what: &str, expected: u16, actual: u16) {
+    if expected != actual {
+        writeln!(
+            report,
+            "    {:<12} expected {:#06X}, got {:#06X}",
+            what, expected, actual
+        )
+        .unwrap();
+    }
+}
+
+/// Runs one case and returns a description of every difference.
+fn run_case(case: &TestCase) -> Result<(), String> {
+    let mut ram = CpuRam::new();
+    for &(addr, data) in &case.initial.ram {
+        ram[addr] = data;
+    }
+    if let Some(ie) = case.initial.ie {
+        ram[0xFFFF] = ie;
+    }
+
+    let mut cpu = Cpu::with_bus(case.initial.registers(), ram);
+    cpu.set_ime(case.initial.ime == Some(1));
+
+    let mut report = String::new();
+
+    //the vectors have no illegal opcodes, so any error is a failure
+    if let Err(err) = cpu.step() {
+        writeln!(report, "    step returned: {}", err).unwrap();
+    }
+
+    let expected = &case.expected;
+    let expected_regs = expected.registers();
+    let actual = cpu.registers();
+    check(&mut report, "AF", expected_regs.af(), actual.af());
+    check(
+        &mut report,
+        "BC",
+        expected_regs.bc.as_both(),
+        actual.bc.as_both(),
+    );
+    check(
+        &mut report,
+        "DE",
+        expected_regs.de.as_both(),
+        actual.de.as_both(),
+    );
+    check(
+        &mut report,
+        "HL",
+        expected_regs.hl.as_both(),
+        actual.hl.as_both(),
+    );
+    check(&mut report, "SP", expected.sp, actual.sp);
+    check(&mut report, "PC", expected.pc, actual.pc);
+    if let Some(ime) = expected.ime {
+        check(&mut report, "IME", ime.into(), cpu.ime().into());
+    }
+
+    for &(addr, data) in &expected.ram {
+        let what = format!("[{:#06X}]", addr);
+        check(&mut report, &what, data.into(), cpu.bus()[addr].into());
+    }
+
+    let expected_cycles: Vec<BusActivity> = case.cycles.iter().map(parse_cycle).collect();
+    let actual_cycles = &cpu.bus().activity;
+    if expected_cycles.len() != actual_cycles.len() {
+        writeln!(
+            report,
+            "    took {} M-cycles, expected {}",
+            actual_cycles.len(),
+            expected_cycles.len()
+        )
+        .unwrap();
+    }
+    for (m_cycle, (expected, actual)) in expected_cycles.iter().zip(actual_cycles).enumerate() {
+        if expected != actual {
+            writeln!(
+                report,
+                "    M-cycle {}: expected {}, got {}",
+                m_cycle + 1,
+                expected,
+                actual
+            )
+            .unwrap();
+        }
+    }
+
+    if report.is_empty() {
+        Ok(())
+    } else {
+        Err(report)
+    }
+}
+
+/// Runs every case in one file. Returns the number of failures and a report of the first few.
+fn run_file(path: &Path) -> Result<(usize, usize, String)> {
+    let json = std::fs::read_to_string(path)?;
+    let cases: Vec<TestCase> =
+        serde_json::from_str(&json).with_context(|| format!("parsing {}", path.display()))?;
+
+    let mut failures = 0;
+    let mut report = String::new();
+    for case in &cases {
+        if let Err(diff) = run_case(case) {
+            if failures < REPORTED_FAILURES {
+                writeln!(report, "  case \"{}\":\n{}", case.name, diff).unwrap();
+            }
+            failures += 1;
+        }
+    }
+
+    Ok((failures, cases.len(), report))
+}
+
+fn vector_dir() -> PathBuf {
+    std::env::var_os("SM83_TESTS_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_DIR))
+}
+
+#[test]
+fn sm83_vectors() -> Result<()> {
+    let dir = vector_dir();
+    if !dir.is_dir() {
+        eprintln!("skipping SM83 test vectors, {} not found", dir.display());
+        return Ok(());
+    }
+
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(&dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<_>>()?;
+    paths.retain(|path| path.extension().is_some_and(|ext| ext == "json"));
+    paths.sort();
+
+    let mut failed_files = Vec::new();
+    for path in &paths {
+        let (failures, total, report) = run_file(path)?;
+        if failures > 0 {
+            let file_name = path.file_name().unwrap().to_string_lossy();
+            eprintln!(
+                "{}: {}/{} cases failed\n{}",
+                file_name, failures, total, report
+            );
+            failed_files.push(file_name.into_owned());
+        }
+    }
+
+    assert!(
+        failed_files.is_empty(),
+        "{}/{} opcode files failed: {}",
+        failed_files.len(),
+        paths.len(),
+        failed_files.join(", ")
+    );
+    Ok(())
+}
+
+#[test]
+fn inline_vector() -> Result<()> {
+    //LD (BC),A
+    let json = r#"{
+        "name": "02 0000",
+        "initial": {
+            "pc": 256, "sp": 65534, "a": 171, "b": 192, "c": 0, "d": 0, "e": 0, "f": 176,
+            "h": 0, "l": 0, "ime": 0, "ie": 0, "ram": [[256, 2]]
+        },
+        "final": {
+            "pc": 257, "sp": 65534, "a": 171, "b": 192, "c": 0, "d": 0, "e": 0, "f": 176,
+            "h": 0, "l": 0, "ime": 0, "ram": [[256, 2], [49152, 171]]
+        },
+        "cycles": [[256, 2, "r-m"], [49152, 171, "-wm"]]
+    }"#;
+    let mut case: TestCase = serde_json::from_str(json)?;
+    assert_eq!(run_case(&case), Ok(()));
+
+    case.expected.pc = 258;
+    case.cycles.pop();
+    let report = run_case(&case).unwrap_err();
+    assert!(report.contains("PC"), "{}", report);
+    assert!(report.contains("took 2 M-cycles, expected 1"), "{}", report);
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use crate::util::Twiddling;
+
+//bit of the internal counter whose falling edge clocks TIMA, indexed by TAC bits 0-1
+const TIMA_BITS: [u8; 4] = [9, 3, 5, 7];
+
+#[derive(Debug, Default)]
+pub struct Timer {
+    counter: u16, //DIV is the upper 8 bits of this
+    tima: u8,
+    tma: u8,
+    tac: u8,
+    //TIMA overflowed during the last M-cycle, reload and interrupt are delayed by one M-cycle
+    overflowed: bool,
+}
+
+impl Timer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    //the boot ROM leaves the counter running, DIV already reads non-zero at 0x0100
+    pub fn with_counter(counter: u16) -> Self {
+        Self {
+            counter,
+            ..Self::default()
+        }
+    }
+
+    /// Advances the timer by one M-cycle. Returns `true` when the timer interrupt should be
+    /// requested.
+    pub fn tick(&mut self) -> bool {
+        let mut interrupt = false;
+
+        if self.overflowed {
+            self.overflowed = false;
+            self.tima = self.tma;
+            interrupt = true;
+        }
+
+        let old_signal = self.signal();
+        self.counter = self.counter.wrapping_add(4);
+        self.detect_falling_edge(old_signal);
+
+        interrupt
+    }
+
+    pub fn read(&self, addr: u16) -> u8 {
+        match addr {
+            0xFF04 => (self.counter >> 8) as u8,
+            0xFF05 => self.tima,
+            0xFF06 => self.tma,
+            0xFF07 => self.tac | 0b1111_1000,
+            _ => unreachable!("{:#06X} is not a timer register", addr),
+        }
+    }
+
+    pub fn write(&mut self, addr: u16, data: u8) {
+        let old_signal = self.signal();
+
+        match addr {
+            //resetting the counter can clock TIMA if the selected bit was set
+            0xFF04 => self.counter = 0,
+            //writing TIMA during the overflow cycle cancels the reload
+            0xFF05 => {
+                self.tima = data;
+                self.overflowed = false;
+            }
+            0xFF06 => self.tma = data,
+            0xFF07 => self.tac = data & 0b0000_0111,
+            _ => unreachable!("{:#06X} is not a timer register", addr),
+        }
+
+        self.detect_falling_edge(old_signal);
+    }
+
+    fn enabled(&self) -> bool {
+        self.tac.get_bit(2)
+    }
+
+    fn signal(&self) -> bool {
+        let bit = TIMA_BITS[(self.tac & 0b11) as usize];
+        let [counter_hi, counter_lo] = self.counter.to_be_bytes();
+        let selected = if bit < 8 {
+            counter_lo.get_bit(bit)
+        } else {
+            counter_hi.get_bit(bit - 8)
+        };
+        self.enabled() && selected
+    }
+
+    fn detect_falling_edge(&mut self, old_signal: bool) {
+        if old_signal && !self.signal() {
+            let (tima, overflowed) = self.tima.overflowing_add(1);
+            self.tima = tima;
+            self.overflowed = overflowed;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn div_increments_every_64_m_cycles() {
+        let mut timer = Timer::new();
+        for _ in 0..63 {
+            timer.tick();
+        }
+        assert_eq!(timer.read(0xFF04), 0);
+        timer.tick();
+        assert_eq!(timer.read(0xFF04), 1);
+    }
+
+    #[test]
+    fn tima_overflow_reloads_one_cycle_late() {
+        let mut timer = Timer::new();
+        timer.write(0xFF06, 0xAB);
+        timer.write(0xFF05, 0xFF);
+        timer.write(0xFF07, 0b101); //enabled, every 4 M-cycles
+
+        for _ in 0..4 {
+            assert!(!timer.tick());
+        }
+        assert_eq!(timer.read(0xFF05), 0x00);
+
+        assert!(timer.tick());
+        assert_eq!(timer.read(0xFF05), 0xAB);
+    }
+
+    #[test]
+    fn div_reset_clocks_tima() {
+        let mut timer = Timer::new();
+        timer.write(0xFF07, 0b101);
+        timer.tick();
+        timer.tick(); //counter bit 3 is now set
+        timer.write(0xFF04, 0);
+        assert_eq!(timer.read(0xFF05), 1);
+    }
+}
@@ -1,4 +1,5 @@
 use std::ops::{Add, AddAssign};
+use std::path::{Path, PathBuf};
 
 pub trait Twiddling {
     fn set_bit(&mut self, n: u8, val: bool);
@@ -27,8 +28,7 @@ impl Twiddling for u8 {
     }
 
     fn swap_nibbles(&mut self) {
-        let swapped = (*self << 4) | (*self >> 4);
-        *self = swapped;
+        *self = self.rotate_right(4);
     }
 }
 
@@ -71,11 +71,29 @@ impl Add<u16> for RegisterPair {
 
 impl AddAssign<u16> for RegisterPair {
     fn add_assign(&mut self, rhs: u16) {
-        let sum = self.as_both() + rhs;
+        let sum = self.as_both().wrapping_add(rhs);
         self.set_both(sum)
     }
 }
 
+/// Every `.gb`/`.gbc` file under `dir`, recursively, in sorted order.
+pub fn find_roms(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut roms = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            roms.extend(find_roms(&path)?);
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext == "gb" || ext == "gbc")
+        {
+            roms.push(path);
+        }
+    }
+    roms.sort();
+    Ok(roms)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;